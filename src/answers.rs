@@ -0,0 +1,105 @@
+use std::fmt;
+use std::path::Path;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::store::{load_or_default, save_creating_dirs, FileFormat};
+use crate::Answer;
+
+/// Known correct answers, keyed by input name (`day3`, `day3_part2`, ...).
+///
+/// Stored as TOML next to the inputs they belong to:
+/// ```toml
+/// [day1]
+/// part1 = 11
+/// part2 = 31
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers(IndexMap<String, PartAnswers>);
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PartAnswers {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part1: Option<Answer>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub part2: Option<Answer>,
+}
+
+/// The outcome of comparing an answer against the stored one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail(Answer),
+    Unknown,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Check::Pass => write!(f, "PASS"),
+            Check::Fail(expected) => write!(f, "FAIL, expected {expected}"),
+            Check::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+impl Answers {
+    pub fn load(path: &Path) -> Result<Self> {
+        load_or_default(path, FileFormat::Toml)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_creating_dirs(self, path, FileFormat::Toml)
+    }
+
+    pub fn get(&self, name: &str, part: u8) -> Option<&Answer> {
+        let parts = self.0.get(name)?;
+        match part {
+            1 => parts.part1.as_ref(),
+            2 => parts.part2.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Whether the input has an entry, even one without answers yet.
+    pub fn contains(&self, name: &str) -> bool {
+        self.0.contains_key(name)
+    }
+
+    /// Adds an empty entry for the input, which the example tests report as pending until its
+    /// answers are filled in. Does nothing when there's already one.
+    pub fn stub(&mut self, name: &str) {
+        self.0.entry(name.to_string()).or_default();
+    }
+
+    /// Whether the input has a stub that no answer has been filled into yet.
+    pub fn is_pending(&self, name: &str) -> bool {
+        self.0
+            .get(name)
+            .is_some_and(|parts| parts.part1.is_none() && parts.part2.is_none())
+    }
+
+    /// Unimplemented parts have no answer, so they are never stored.
+    pub fn set(&mut self, name: &str, part: u8, answer: Answer) {
+        if answer == Answer::Unimplemented {
+            return;
+        }
+
+        let parts = self.0.entry(name.to_string()).or_default();
+        match part {
+            1 => parts.part1 = Some(answer),
+            2 => parts.part2 = Some(answer),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn check(&self, name: &str, part: u8, answer: &Answer) -> Check {
+        match self.get(name, part) {
+            Some(expected) if expected == answer => Check::Pass,
+            Some(expected) => Check::Fail(expected.clone()),
+            None => Check::Unknown,
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Result};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::store::{load_or_default, save_creating_dirs, FileFormat};
+use crate::{DayReport, PartReport};
+
+/// How long to run a part before measuring, so that caches and the allocator are warm.
+const WARMUP: Duration = Duration::from_millis(200);
+/// Roughly how long to spend measuring each part.
+const MEASURE: Duration = Duration::from_secs(2);
+const MIN_ITERATIONS: usize = 10;
+const MAX_ITERATIONS: usize = 100_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// `samples` must not be empty.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let n = samples.len();
+
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / (n - 1).max(1) as f64;
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        // nearest-rank percentile
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+
+        Stats {
+            iterations: n,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_secs_f64(variance.sqrt()),
+            p95,
+        }
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "min {:?} | median {:?} | mean {:?} ± {:?} | p95 {:?} ({} runs)",
+            self.min, self.median, self.mean, self.stddev, self.p95, self.iterations
+        )
+    }
+}
+
+/// Runs `f` repeatedly: first for a warmup period, then for as many iterations as fit in the
+/// measurement period based on how long the warmup runs took. Stops at the first error.
+pub fn bench<I: ?Sized, T, E>(f: impl Fn(&I) -> Result<T, E>, input: &I) -> Result<(T, Stats), E> {
+    let warmup_start = Instant::now();
+    let mut warmup_runs = 0;
+    let mut output;
+    loop {
+        output = f(input)?;
+        warmup_runs += 1;
+        if warmup_start.elapsed() >= WARMUP {
+            break;
+        }
+    }
+
+    let estimate = warmup_start.elapsed() / warmup_runs;
+    let iterations = (MEASURE.as_nanos() / estimate.as_nanos().max(1))
+        .clamp(MIN_ITERATIONS as u128, MAX_ITERATIONS as u128) as usize;
+
+    let samples = (0..iterations)
+        .map(|_| {
+            let start = Instant::now();
+            let output = f(input)?;
+            let elapsed = start.elapsed();
+            // dropping a big parsed input can take a while, and isn't what's being measured
+            drop(output);
+            Ok(elapsed)
+        })
+        .collect::<Result<_, E>>()?;
+
+    Ok((output, Stats::from_samples(samples)))
+}
+
+/// What a [`BenchRecord`] timed: one of the parts, or parsing the input (`"parse"`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+enum Step {
+    Part(u8),
+    Other(String),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BenchRecord {
+    #[serde(default)]
+    year: u16,
+    day: u8,
+    /// The name of the input file the step ran against.
+    #[serde(default)]
+    input: String,
+    part: Step,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    variant: Option<String>,
+    iterations: usize,
+    min_ns: u64,
+    median_ns: u64,
+    mean_ns: u64,
+    stddev_ns: u64,
+    p95_ns: u64,
+}
+
+impl BenchRecord {
+    fn new(year: u16, day: u8, input: &str, part: Step, stats: Stats) -> Self {
+        BenchRecord {
+            year,
+            day,
+            input: input.to_string(),
+            part,
+            variant: None,
+            iterations: stats.iterations,
+            min_ns: stats.min.as_nanos() as u64,
+            median_ns: stats.median.as_nanos() as u64,
+            mean_ns: stats.mean.as_nanos() as u64,
+            stddev_ns: stats.stddev.as_nanos() as u64,
+            p95_ns: stats.p95.as_nanos() as u64,
+        }
+    }
+
+    fn parse(report: &DayReport, stats: Stats) -> Self {
+        let step = Step::Other("parse".to_string());
+        BenchRecord::new(report.year, report.day, &report.input, step, stats)
+    }
+
+    fn part(report: &PartReport, stats: Stats) -> Self {
+        let step = Step::Part(report.part);
+        BenchRecord {
+            variant: report.variant.map(str::to_string),
+            ..BenchRecord::new(report.year, report.day, &report.input, step, stats)
+        }
+    }
+
+    /// Where the record is kept in a [`Baseline`]: `2024/real/day9/part2`,
+    /// `2024/example/day7/part1_recurse`, `2024/example/day3_part2/parse`, ... `kind` tells the
+    /// real input, the examples and `--input` files apart, since they can share a name.
+    fn key(&self, kind: &str) -> String {
+        let step = match (&self.part, &self.variant) {
+            (Step::Part(part), Some(variant)) => format!("part{part}_{variant}"),
+            (Step::Part(part), None) => format!("part{part}"),
+            (Step::Other(step), _) => step.clone(),
+        };
+        format!("{}/{kind}/{}/{step}", self.year, self.input)
+    }
+}
+
+/// The records of every benchmarked step, parsing first and then the parts, for each input.
+fn records(reports: &[DayReport]) -> Vec<BenchRecord> {
+    reports
+        .iter()
+        .flat_map(|report| {
+            let parse = report
+                .parse_stats
+                .map(|stats| BenchRecord::parse(report, stats));
+            let parts = report
+                .parts
+                .iter()
+                .filter_map(|part| Some(BenchRecord::part(part, part.stats?)));
+            parse.into_iter().chain(parts)
+        })
+        .collect()
+}
+
+/// Writes the stats of every benchmarked step as a JSON array, to stdout when `path` is `-`.
+pub fn write_json(path: &Path, reports: &[DayReport]) -> Result<()> {
+    let json = serde_json::to_string_pretty(&records(reports))?;
+    if path == Path::new("-") {
+        println!("{json}");
+    } else {
+        fs::write(path, json)?;
+    }
+
+    Ok(())
+}
+
+/// Saved benchmark results to compare later runs against, keyed by year, kind of input, input and
+/// step (see [`BenchRecord::key`]).
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline(IndexMap<String, BenchRecord>);
+
+/// How a part's median time compares to its baseline, as a percentage change.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Regressed(f64),
+    Improved(f64),
+    Unchanged(f64),
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Regressed(change) => write!(f, "regressed by {change:.1}%"),
+            Verdict::Improved(change) => write!(f, "improved by {:.1}%", -change),
+            Verdict::Unchanged(change) => write!(f, "unchanged ({change:+.1}%)"),
+            Verdict::Missing => write!(f, "no baseline"),
+        }
+    }
+}
+
+impl Baseline {
+    /// Reads a baseline to compare against, which has to have been saved before.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            bail!(
+                "There's no baseline at {}, save one with --save-baseline first",
+                path.display()
+            );
+        }
+        load_or_default(path, FileFormat::Json)
+    }
+
+    /// Reads a baseline to add results to, starting a new one when there's none yet.
+    pub fn load_or_default(path: &Path) -> Result<Self> {
+        load_or_default(path, FileFormat::Json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_creating_dirs(self, path, FileFormat::Json)
+    }
+
+    /// Keeps every benchmarked step of the reports, replacing what was saved for them before.
+    /// `kind` is `real`, `example` or `input`, depending on where the inputs came from.
+    pub fn record(&mut self, kind: &str, reports: &[DayReport]) {
+        for record in records(reports) {
+            self.0.insert(record.key(kind), record);
+        }
+    }
+
+    /// Compares medians, since they are less affected by the odd slow run than means are.
+    /// Changes within `threshold` percent either way count as unchanged.
+    pub fn compare(&self, kind: &str, part: &PartReport, stats: &Stats, threshold: f64) -> Verdict {
+        let key = BenchRecord::part(part, *stats).key(kind);
+        // a zero median can't be compared against, and only comes from a broken file
+        let Some(record) = self.0.get(&key).filter(|record| record.median_ns > 0) else {
+            return Verdict::Missing;
+        };
+
+        let old = record.median_ns as f64;
+        let change = (stats.median.as_nanos() as f64 - old) / old * 100.0;
+        if change > threshold {
+            Verdict::Regressed(change)
+        } else if change < -threshold {
+            Verdict::Improved(change)
+        } else {
+            Verdict::Unchanged(change)
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, Once, OnceLock};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::{anyhow, Result};
+use reqwest::blocking::{Client, RequestBuilder, Response};
+use reqwest::header::COOKIE;
+use reqwest::StatusCode;
+
+use crate::error::Error;
+use crate::session::session_token;
+use crate::submit::Outcome;
+use crate::unlock::ensure_unlocked;
+use crate::{config, make_state_dir};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// How many times a request is sent again while the site answers with a server error.
+const RETRIES: u32 = 3;
+
+/// When the last request of this process was sent, shared by every client so that throttling
+/// holds across them.
+static LAST_REQUEST: Mutex<Option<SystemTime>> = Mutex::new(None);
+
+static SHARED: OnceLock<AocClient> = OnceLock::new();
+
+/// Makes sure a missing contact is only pointed out once per run.
+static CONTACT_WARNING: Once = Once::new();
+
+/// Why a downloaded input was turned down, told apart by the response's status and body.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DownloadError {
+    /// The session token is missing, has expired, or isn't valid.
+    LoggedOut,
+    /// The puzzle hasn't unlocked yet.
+    NotUnlocked,
+    /// The site wants fewer requests.
+    RateLimited,
+    NotFound,
+    /// Any other unsuccessful status.
+    Status(u16),
+    /// A successful response whose body doesn't look like a puzzle input, such as an HTML page.
+    NotAnInput,
+}
+
+impl fmt::Display for DownloadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DownloadError::LoggedOut => write!(
+                f,
+                "the site doesn't accept the session token, it may have expired"
+            ),
+            DownloadError::NotUnlocked => write!(f, "the puzzle hasn't unlocked yet"),
+            DownloadError::RateLimited => write!(f, "the site is rate limiting requests"),
+            DownloadError::NotFound => write!(f, "there's no such puzzle"),
+            DownloadError::Status(status) => write!(f, "the site answered with status {status}"),
+            DownloadError::NotAnInput => {
+                write!(f, "the site sent back something that isn't an input")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DownloadError {}
+
+/// Tells whether a response to an input request holds an actual input. The site's refusals come
+/// with their own explanation, which is what gives them away even when the status doesn't.
+pub fn check_input(status: u16, body: &str) -> Result<(), DownloadError> {
+    let body = body.trim_start();
+    if body.starts_with("Puzzle inputs differ by user") {
+        return Err(DownloadError::LoggedOut);
+    }
+    if body.starts_with("Please don't repeatedly request this endpoint before it unlocks") {
+        return Err(DownloadError::NotUnlocked);
+    }
+
+    match StatusCode::from_u16(status) {
+        Ok(StatusCode::TOO_MANY_REQUESTS) => Err(DownloadError::RateLimited),
+        Ok(StatusCode::NOT_FOUND) => Err(DownloadError::NotFound),
+        Ok(status) if status.is_success() => match body {
+            "" => Err(DownloadError::NotAnInput),
+            _ if is_html(body) => Err(DownloadError::NotAnInput),
+            _ => Ok(()),
+        },
+        _ => Err(DownloadError::Status(status)),
+    }
+}
+
+/// Whether the body is a web page rather than an input. Inputs can start with `<` too, as in
+/// puzzles about brackets, so only a document's opening tag counts.
+fn is_html(body: &str) -> bool {
+    ["<!doctype", "<html"].iter().any(|tag| {
+        body.get(..tag.len())
+            .is_some_and(|start| start.eq_ignore_ascii_case(tag))
+    })
+}
+
+/// A client for the Advent of Code website, or anything that behaves like it (see the `mock`
+/// feature's `MockServer`).
+pub struct AocClient {
+    http: Client,
+    base_url: String,
+    session: Option<String>,
+    min_interval: Duration,
+    /// Where the time of the last request is kept, so that throttling holds across runs.
+    last_request_file: Option<PathBuf>,
+    retry_delay: Duration,
+}
+
+impl AocClient {
+    /// Sends the User-Agent from `aoc.toml`.
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        AocClient {
+            http: make_http_client(&config().user_agent()),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            min_interval: Duration::ZERO,
+            last_request_file: None,
+            retry_delay: Duration::from_secs(1),
+        }
+    }
+
+    /// Talks to `AOC_BASE_URL` when it's set, or the real site otherwise, using the `AOC_SESSION`
+    /// token or the `session_file` from `aoc.toml`. Requests are throttled as configured there,
+    /// across runs too. Warns once when the real site would get no contact in the User-Agent.
+    pub fn from_env() -> Self {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.to_string());
+        if base_url == DEFAULT_BASE_URL && config().requests.contact.is_none() {
+            CONTACT_WARNING.call_once(|| {
+                eprintln!(
+                    "Warning: set `contact` under [requests] in aoc.toml, so that the site can \
+                     reach you about the requests this sends"
+                );
+            });
+        }
+        Self::new(&base_url, session_token())
+            .with_min_interval(config().min_interval())
+            .with_last_request_file(make_state_dir().join("last_request"))
+    }
+
+    /// The client that downloads, puzzle fetches and submissions all go through, made with
+    /// [`AocClient::from_env`] on first use.
+    pub fn shared() -> &'static Self {
+        SHARED.get_or_init(Self::from_env)
+    }
+
+    pub fn with_session(mut self, token: String) -> Self {
+        self.session = Some(token);
+        self
+    }
+
+    pub fn with_user_agent(mut self, user_agent: &str) -> Self {
+        self.http = make_http_client(user_agent);
+        self
+    }
+
+    /// Waits until at least `interval` has passed since the previous request before sending
+    /// another one.
+    pub fn with_min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Keeps the time of the last request in this file, so that the minimum interval also holds
+    /// between runs.
+    pub fn with_last_request_file(mut self, path: PathBuf) -> Self {
+        self.last_request_file = Some(path);
+        self
+    }
+
+    /// How long to wait before sending a request again after a server error. The wait doubles
+    /// with each retry.
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Fails with a [`DownloadError`] when the site sends back anything but an input.
+    pub fn download_input(&self, year: u16, day: u8) -> Result<String> {
+        ensure_unlocked(year, day)?;
+        let req = self
+            .http
+            .get(self.make_url(&format!("/{year}/day/{day}/input")))
+            .header(COOKIE, self.cookie()?);
+
+        let resp = self.send(req)?;
+        let status = resp.status().as_u16();
+        let body = resp.text()?;
+        check_input(status, &body)?;
+
+        Ok(body)
+    }
+
+    /// The puzzle description page. Part 2 is only on it once part 1 is solved, which needs the
+    /// session token, but part 1 can be fetched without one.
+    pub fn download_puzzle(&self, year: u16, day: u8) -> Result<String> {
+        ensure_unlocked(year, day)?;
+        let mut req = self.http.get(self.make_url(&format!("/{year}/day/{day}")));
+        if let Ok(cookie) = self.cookie() {
+            req = req.header(COOKIE, cookie);
+        }
+
+        let resp = self.send(req)?;
+        match resp.status() {
+            StatusCode::NOT_FOUND => Err(DownloadError::NotFound.into()),
+            status if !status.is_success() => Err(DownloadError::Status(status.as_u16()).into()),
+            _ => Ok(resp.text()?),
+        }
+    }
+
+    /// The name of the user the session token belongs to, read from the settings page. Fails with
+    /// [`DownloadError::LoggedOut`] when the site doesn't accept the token.
+    pub fn whoami(&self, year: u16) -> Result<String> {
+        let req = self
+            .http
+            .get(self.make_url(&format!("/{year}/settings")))
+            .header(COOKIE, self.cookie()?);
+
+        let resp = self.send(req)?;
+        let status = resp.status();
+        let html = resp.text()?;
+        match parse_user(&html) {
+            Some(user) => Ok(user),
+            None if status.is_success() => Err(DownloadError::LoggedOut.into()),
+            None => Err(DownloadError::Status(status.as_u16()).into()),
+        }
+    }
+
+    /// Posts an answer for one part of a day, and reads the outcome from the returned page. The
+    /// answer is only ever sent once: a server error may come from a proxy after the site has
+    /// already taken it, and sending it again would only get "answered too recently" back.
+    pub fn submit_answer(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome> {
+        ensure_unlocked(year, day)?;
+        let req = self
+            .http
+            .post(self.make_url(&format!("/{year}/day/{day}/answer")))
+            .header(COOKIE, self.cookie()?)
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)]);
+
+        let resp = self.send_once(req)?;
+        let status = resp.status();
+        if !status.is_success() {
+            return Err(DownloadError::Status(status.as_u16()).into());
+        }
+        Outcome::parse(&resp.text()?)
+    }
+
+    /// Sends a request once throttling allows it, and sends it again after a growing wait for
+    /// as long as the site answers with a server error, up to [`RETRIES`] times. Only for
+    /// requests that are safe to repeat.
+    fn send(&self, req: RequestBuilder) -> Result<Response> {
+        let mut delay = self.retry_delay;
+        for _ in 0..RETRIES {
+            let attempt = req
+                .try_clone()
+                .ok_or_else(|| anyhow!("Couldn't copy the request to retry it"))?;
+            self.throttle();
+            let resp = attempt.send()?;
+            if !resp.status().is_server_error() {
+                return Ok(resp);
+            }
+
+            eprintln!(
+                "The site answered with {}, trying again in {delay:?}",
+                resp.status()
+            );
+            thread::sleep(delay);
+            delay *= 2;
+        }
+
+        self.send_once(req)
+    }
+
+    /// Sends a request once throttling allows it.
+    fn send_once(&self, req: RequestBuilder) -> Result<Response> {
+        self.throttle();
+        Ok(req.send()?)
+    }
+
+    fn throttle(&self) {
+        let mut last = LAST_REQUEST.lock().unwrap_or_else(|err| err.into_inner());
+        let persisted = self
+            .last_request_file
+            .as_deref()
+            .and_then(read_last_request);
+        if let Some(wait) = last.max(persisted).and_then(|last| {
+            (last + self.min_interval)
+                .duration_since(SystemTime::now())
+                .ok()
+        }) {
+            thread::sleep(wait);
+        }
+
+        let now = SystemTime::now();
+        *last = Some(now);
+        if let Some(path) = &self.last_request_file {
+            if let Err(err) = write_last_request(path, now) {
+                eprintln!("Couldn't save the time of the last request: {err:#}");
+            }
+        }
+    }
+
+    fn make_url(&self, path: &str) -> String {
+        format!("{}{path}", self.base_url)
+    }
+
+    fn cookie(&self) -> Result<String> {
+        let session = self.session.as_ref().ok_or(Error::NoSession)?;
+
+        Ok(format!("session={session}"))
+    }
+}
+
+fn make_http_client(user_agent: &str) -> Client {
+    Client::builder()
+        .user_agent(user_agent)
+        .build()
+        .expect("the HTTP client's settings are valid")
+}
+
+/// The file holds the milliseconds since the Unix epoch.
+fn read_last_request(path: &Path) -> Option<SystemTime> {
+    let millis = fs::read_to_string(path).ok()?.trim().parse().ok()?;
+    Some(UNIX_EPOCH + Duration::from_millis(millis))
+}
+
+fn write_last_request(path: &Path, time: SystemTime) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let millis = time.duration_since(UNIX_EPOCH)?.as_millis();
+    fs::write(path, millis.to_string())?;
+
+    Ok(())
+}
+
+/// Every page shows who's logged in, as `<div class="user">name <span class="star-count">..`.
+/// Anonymous users show up as `(anonymous user #123)`.
+pub fn parse_user(html: &str) -> Option<String> {
+    let (_, rest) = html.split_once("<div class=\"user\">")?;
+    let user = rest.split('<').next()?.trim();
+
+    Some(user.to_string()).filter(|user| !user.is_empty())
+}
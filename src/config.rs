@@ -0,0 +1,129 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::Result;
+use serde::Deserialize;
+
+use crate::store::{load_or_default, FileFormat};
+
+/// The project's settings, read from `aoc.toml` in the project root (or the file `AOC_CONFIG`
+/// points at). Everything is optional, and a missing file is the same as an empty one:
+/// ```toml
+/// year = 2024
+/// inputs_dir = "inputs"
+/// session_file = ".aoc/session"
+///
+/// [defaults]
+/// real = false
+/// bench = false
+/// threshold = 5.0
+///
+/// [requests]
+/// min_interval_ms = 1000
+/// contact = "you@example.com"
+/// ```
+/// Relative paths are relative to the project root.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The year to run when `--year` isn't passed.
+    pub year: u16,
+    /// Holds a `<year>/{real,example}` folder per year.
+    pub inputs_dir: PathBuf,
+    /// A file holding the session token, used when `AOC_SESSION` isn't set.
+    pub session_file: Option<PathBuf>,
+    pub defaults: Defaults,
+    pub requests: Requests,
+}
+
+/// Flags that apply to every run, as if they were passed on the command line.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Defaults {
+    pub real: bool,
+    pub bench: bool,
+    /// Used when `--threshold` isn't passed.
+    pub threshold: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Requests {
+    /// The least time to leave between two requests to the site, even across runs.
+    pub min_interval_ms: u64,
+    /// How the site can reach whoever runs this, added to the User-Agent.
+    pub contact: Option<String>,
+    /// Replaces the crate's name and version at the start of the User-Agent.
+    pub user_agent: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: 2024,
+            inputs_dir: PathBuf::from("inputs"),
+            session_file: None,
+            defaults: Defaults::default(),
+            requests: Requests::default(),
+        }
+    }
+}
+
+impl Default for Requests {
+    fn default() -> Self {
+        Requests {
+            min_interval_ms: 1000,
+            contact: None,
+            user_agent: None,
+        }
+    }
+}
+
+impl Config {
+    /// Loads the project's config file.
+    pub fn load() -> Result<Self> {
+        Self::load_from(&config_path())
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        load_or_default(path, FileFormat::Toml)
+    }
+
+    pub fn inputs_dir(&self) -> PathBuf {
+        project_root().join(&self.inputs_dir)
+    }
+
+    pub fn session_file(&self) -> Option<PathBuf> {
+        self.session_file
+            .as_ref()
+            .map(|path| project_root().join(path))
+    }
+
+    pub fn min_interval(&self) -> Duration {
+        Duration::from_millis(self.requests.min_interval_ms)
+    }
+
+    /// The User-Agent sent with every request, like `adventofcode-2024/0.1.12 (you@example.com)`.
+    pub fn user_agent(&self) -> String {
+        let agent = self.requests.user_agent.as_deref().unwrap_or(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ));
+        match &self.requests.contact {
+            Some(contact) => format!("{agent} ({contact})"),
+            None => agent.to_string(),
+        }
+    }
+}
+
+fn project_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+}
+
+fn config_path() -> PathBuf {
+    env::var_os("AOC_CONFIG")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| project_root().join("aoc.toml"))
+}
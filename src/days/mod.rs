@@ -0,0 +1,30 @@
+use crate::Day;
+
+pub mod y2024;
+
+/// The solved days of one year.
+pub struct Year {
+    pub year: u16,
+    pub days: &'static [Day],
+}
+
+impl Year {
+    pub const fn new(year: u16, days: &'static [Day]) -> Self {
+        Year { year, days }
+    }
+}
+
+/// Every year with solved days, oldest first.
+pub const YEARS: &[Year] = &[Year::new(2024, y2024::DAYS)];
+
+/// The solved days of a year, which is empty for a year that hasn't been started.
+pub fn days(year: u16) -> &'static [Day] {
+    YEARS
+        .iter()
+        .find(|y| y.year == year)
+        .map_or(&[], |y| y.days)
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Day> {
+    days(year).iter().find(|d| d.day == day)
+}
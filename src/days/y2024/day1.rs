@@ -0,0 +1,54 @@
+use crate::{Answer, Solution};
+use itertools::Itertools;
+
+fn parse_input(input: &str) -> (Vec<i32>, Vec<i32>) {
+    input
+        .lines()
+        .map(|line| {
+            line.split_ascii_whitespace()
+                .map(|num| num.parse::<i32>().unwrap())
+                .collect_tuple()
+                .unwrap()
+        })
+        .unzip()
+}
+
+fn part1((l, r): &(Vec<i32>, Vec<i32>)) -> Answer {
+    let total_distance = l
+        .iter()
+        .sorted()
+        .zip(r.iter().sorted())
+        .fold(0, |acc, (a, b)| acc + (a - b).abs());
+
+    total_distance.into()
+}
+
+fn part2((l, r): &(Vec<i32>, Vec<i32>)) -> Answer {
+    let r_counts = r.iter().counts();
+
+    let similarity_score: i32 = l
+        .iter()
+        .filter_map(|x| r_counts.get(x).map(|&count| count as i32 * x))
+        .sum();
+
+    similarity_score.into()
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = (Vec<i32>, Vec<i32>);
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
+}
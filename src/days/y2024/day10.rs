@@ -1,8 +1,8 @@
 use std::collections::VecDeque;
 
-use adventofcode_2024::{
-    runner,
+use crate::{
     utils::{GridUtils, Loc, DIR4},
+    Answer, Solution,
 };
 use grid::Grid;
 use hashbrown::HashSet;
@@ -15,9 +15,7 @@ fn parse_input(input: &str) -> (Grid<char>, HashSet<Loc>) {
     (grid, trailheads)
 }
 
-fn part1(input: &str) {
-    let (map, trailheads) = parse_input(input);
-
+fn part1((map, trailheads): &(Grid<char>, HashSet<Loc>)) -> Answer {
     let total_score: i32 = trailheads
         .iter()
         .map(|start| {
@@ -33,7 +31,7 @@ fn part1(input: &str) {
                     .map(|dir| loc + *dir)
                     .filter(|&new_loc| {
                         new_loc.in_bounds(map.size())
-                            && (map[new_loc.into()] as u8 - map[loc.into()] as u8) == 1
+                            && (map[new_loc.into()] as u8).wrapping_sub(map[loc.into()] as u8) == 1
                             && !queue.contains(&new_loc)
                     })
                     .collect_vec();
@@ -44,12 +42,10 @@ fn part1(input: &str) {
         })
         .sum();
 
-    println!("Day 10 Part 1: {}", total_score);
+    total_score.into()
 }
 
-fn part2(input: &str) {
-    let (map, trailheads) = parse_input(input);
-
+fn part2((map, trailheads): &(Grid<char>, HashSet<Loc>)) -> Answer {
     let total_score: i32 = trailheads
         .iter()
         .map(|start| {
@@ -62,17 +58,31 @@ fn part2(input: &str) {
 
                 queue.extend(DIR4.iter().map(|dir| loc + *dir).filter(|&new_loc| {
                     new_loc.in_bounds(map.size())
-                        && (map[new_loc.into()] as u8 - map[loc.into()] as u8) == 1
+                        && (map[new_loc.into()] as u8).wrapping_sub(map[loc.into()] as u8) == 1
                 }));
             }
             score
         })
         .sum();
 
-    println!("Day 10 Part 2: {}", total_score);
+    total_score.into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = (Grid<char>, HashSet<Loc>);
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
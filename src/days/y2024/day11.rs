@@ -1,4 +1,4 @@
-use adventofcode_2024::runner;
+use crate::{Answer, Solution};
 use fnv::FnvHashMap;
 use itertools::Itertools;
 
@@ -18,7 +18,7 @@ impl IntUtils for StoneId {
     }
 
     fn split_half(self) -> (Self, Self) {
-        assert!(self.n_digits() % 2 == 0);
+        assert!(self.n_digits().is_multiple_of(2));
         let mask = 10u64.pow(self.n_digits() / 2);
         (self / mask, self % mask)
     }
@@ -36,7 +36,7 @@ fn parse_input(input: &str) -> FnvHashMap<StoneId, Count> {
 fn apply_rules(stone: StoneId) -> (StoneId, Option<StoneId>) {
     match stone {
         0 => (1, None),
-        val if stone.n_digits() % 2 == 0 => (val.split_half().0, Some(val.split_half().1)),
+        val if stone.n_digits().is_multiple_of(2) => (val.split_half().0, Some(val.split_half().1)),
         _ => (stone * 2024, None),
     }
 }
@@ -63,17 +63,29 @@ fn compute(n_steps: usize, mut stones: FnvHashMap<StoneId, Count>) -> Count {
     stones.values().sum()
 }
 
-fn part1(input: &str) {
-    let total = compute(25, parse_input(input));
-    println!("Day 11 Part 1: {}", total);
+fn part1(stones: &FnvHashMap<StoneId, Count>) -> Answer {
+    compute(25, stones.clone()).into()
 }
 
-fn part2(input: &str) {
-    let total = compute(75, parse_input(input));
-    println!("Day 11 Part 2: {}", total);
+fn part2(stones: &FnvHashMap<StoneId, Count>) -> Answer {
+    compute(75, stones.clone()).into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = FnvHashMap<StoneId, Count>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
@@ -0,0 +1,69 @@
+use crate::{Answer, Solution};
+use anyhow::Context;
+use itertools::{any, Itertools};
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+fn parse_input(input: &str) -> anyhow::Result<Vec<Vec<i32>>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.split_ascii_whitespace()
+                .map(|num| {
+                    num.parse::<i32>()
+                        .with_context(|| format!("Report {} has a bad level {num:?}", i + 1))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn safe_check(line: &[i32]) -> bool {
+    let diffs = line
+        .iter()
+        .tuple_windows()
+        .map(|(a, b)| a - b)
+        .collect_vec();
+
+    diffs.iter().all(|&x| (1..=3).contains(&x)) || diffs.iter().all(|&x| (-3..=-1).contains(&x))
+}
+
+fn part1(reports: &[Vec<i32>]) -> Answer {
+    let safe_count = reports.iter().filter(|line| safe_check(line)).count();
+
+    safe_count.into()
+}
+
+fn part2(reports: &[Vec<i32>]) -> Answer {
+    let safe_count = reports
+        .par_iter()
+        .filter(|line| {
+            safe_check(line)
+                || any(
+                    (0..line.len()).map(|i| [&line[..i], &line[i + 1..]].concat()),
+                    |iter| safe_check(&iter),
+                )
+        })
+        .count();
+
+    safe_count.into()
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
+}
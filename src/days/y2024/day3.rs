@@ -1,7 +1,7 @@
-use adventofcode_2024::runner;
+use crate::{Answer, Solution};
 use regex::Regex;
 
-fn part1(input: &str) {
+fn part1(input: &str) -> Answer {
     let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
     let total = re
         .captures_iter(input)
@@ -11,10 +11,10 @@ fn part1(input: &str) {
         })
         .fold(0, |acc, (l, r)| acc + l * r);
 
-    println!("Day 3 Part 1: {}", total);
+    total.into()
 }
 
-fn part2(input: &str) {
+fn part2(input: &str) -> Answer {
     let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)|don't|do").unwrap();
     let mut enabled = true;
     let total = re
@@ -36,10 +36,24 @@ fn part2(input: &str) {
         })
         .fold(0, |acc, (l, r)| acc + l * r);
 
-    println!("Day 3 Part 2: {}", total);
+    total.into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = String;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(input.to_string())
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
@@ -1,4 +1,4 @@
-use adventofcode_2024::runner;
+use crate::{Answer, Solution};
 use grid::Grid;
 use itertools::Itertools;
 
@@ -38,16 +38,15 @@ fn get_words(grid: &Grid<char>, r: usize, c: usize) -> Vec<String> {
     words
 }
 
-fn part1(input: &str) {
-    let grid = parse_input(input);
+fn part1(grid: &Grid<char>) -> Answer {
     let total_xmas = (0..grid.rows())
         .cartesian_product(0..grid.cols())
         .filter(|&(r, c)| grid[(r, c)] == 'X')
-        .flat_map(|(r, c)| get_words(&grid, r, c))
+        .flat_map(|(r, c)| get_words(grid, r, c))
         .filter(|word| word == "XMAS")
         .count();
 
-    println!("Day 4 Part 1: {:?}", total_xmas);
+    total_xmas.into()
 }
 
 fn check_diagonal_sam(chars: [Option<&char>; 3]) -> bool {
@@ -55,8 +54,7 @@ fn check_diagonal_sam(chars: [Option<&char>; 3]) -> bool {
     word == "MAS" || word == "SAM"
 }
 
-fn part2(input: &str) {
-    let grid = parse_input(input);
+fn part2(grid: &Grid<char>) -> Answer {
     let mut total_cross_mas = 0;
 
     for (r, c) in (0..grid.rows()).cartesian_product(0..grid.cols()) {
@@ -65,15 +63,15 @@ fn part2(input: &str) {
         }
 
         let nw_se = [
-            grid.get(r - 1, c - 1),
+            grid.get(r.wrapping_sub(1), c.wrapping_sub(1)),
             grid.get(r, c),
             grid.get(r + 1, c + 1),
         ];
 
         let ne_sw = [
-            grid.get(r - 1, c + 1),
+            grid.get(r.wrapping_sub(1), c + 1),
             grid.get(r, c),
-            grid.get(r + 1, c - 1),
+            grid.get(r + 1, c.wrapping_sub(1)),
         ];
 
         if check_diagonal_sam(nw_se) && check_diagonal_sam(ne_sw) {
@@ -81,10 +79,24 @@ fn part2(input: &str) {
         }
     }
 
-    println!("Day 4 Part 2: {}", total_cross_mas);
+    total_cross_mas.into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Grid<char>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
@@ -1,10 +1,12 @@
 use std::cmp::Ordering;
 
-use adventofcode_2024::runner;
+use crate::{Answer, Solution};
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
-fn parse_input(input: &str) -> (HashMap<i32, HashSet<i32>>, Vec<Vec<i32>>) {
+type Rules = HashMap<i32, HashSet<i32>>;
+
+fn parse_input(input: &str) -> (Rules, Vec<Vec<i32>>) {
     let (rules, updates) = input.split_once("\n\n").unwrap();
 
     /* Rules Map Example:
@@ -34,9 +36,7 @@ fn parse_input(input: &str) -> (HashMap<i32, HashSet<i32>>, Vec<Vec<i32>>) {
     (rules_map, updates_vec)
 }
 
-fn part1(input: &str) {
-    let (rules_map, updates) = parse_input(input);
-
+fn part1((rules_map, updates): &(Rules, Vec<Vec<i32>>)) -> Answer {
     let mut valids = vec![];
     'outer: for v in updates.iter() {
         for (i, vv) in v.iter().enumerate() {
@@ -52,12 +52,10 @@ fn part1(input: &str) {
 
     let total: i32 = valids.iter().map(|v| v[v.len() / 2]).sum();
 
-    println!("Day 5 Part 1: {}", total);
+    total.into()
 }
 
-fn part2(input: &str) {
-    let (rules_map, updates) = parse_input(input);
-
+fn part2((rules_map, updates): &(Rules, Vec<Vec<i32>>)) -> Answer {
     let mut invalids = vec![];
     'outer: for v in updates.iter() {
         for (i, vv) in v.iter().enumerate() {
@@ -86,10 +84,24 @@ fn part2(input: &str) {
 
     let total: i32 = new_invalids.iter().map(|v| v[v.len() / 2]).sum();
 
-    println!("Day 5 Part 2: {}", total);
+    total.into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = (Rules, Vec<Vec<i32>>);
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
@@ -1,6 +1,6 @@
-use adventofcode_2024::{
-    runner,
+use crate::{
     utils::{Dir, GridUtils, Loc},
+    Answer, Solution,
 };
 use fnv::FnvHashSet;
 use grid::Grid;
@@ -83,16 +83,15 @@ fn parse_input(input: &str) -> (Grid<char>, Loc) {
     (grid, loc)
 }
 
-fn part1(input: &str) {
-    let (grid, starting_pos) = parse_input(input);
-    let walked_locs = walk(&grid, &starting_pos);
+fn part1((grid, starting_pos): &(Grid<char>, Loc)) -> Answer {
+    let walked_locs = walk(grid, starting_pos);
 
-    println!("Day 6 Part 1: {}", walked_locs.len());
+    walked_locs.len().into()
 }
 
-fn part2(input: &str) {
-    let (grid, starting_pos) = parse_input(input);
-    let mut walked_locs: FnvHashSet<Loc> = walk(&grid, &starting_pos);
+fn part2((grid, starting_pos): &(Grid<char>, Loc)) -> Answer {
+    let starting_pos = *starting_pos;
+    let mut walked_locs: FnvHashSet<Loc> = walk(grid, &starting_pos);
 
     walked_locs.remove(&starting_pos);
 
@@ -132,10 +131,24 @@ fn part2(input: &str) {
         })
         .count();
 
-    println!("Day 6 Part 2: {}", cycle_count);
+    cycle_count.into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = (Grid<char>, Loc);
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
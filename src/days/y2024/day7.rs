@@ -1,4 +1,4 @@
-use adventofcode_2024::runner;
+use crate::{solve, Answer, Solution, Variant};
 use dashmap::DashMap;
 use itertools::Itertools;
 use rayon::prelude::*;
@@ -79,8 +79,8 @@ fn compute_recurse(vals: &[u64], op_options: &[Op], target: u64) -> Option<u64>
         .find_map(|op| recurse(vals[0], &vals[1..], *op, op_options, target))
 }
 
-fn part1(input: &str) {
-    let total: u64 = parse_input(input)
+fn part1(equations: &[(u64, Vec<u64>)]) -> Answer {
+    let total: u64 = equations
         .par_iter()
         .filter_map(|(target, components)| {
             let ops = generate_op_matrix_2(components.len() - 1);
@@ -93,23 +93,24 @@ fn part1(input: &str) {
             })
         })
         .sum();
-    println!("Day 7 Part 1: {}", total);
+    total.into()
 }
 
-fn part1_recurse(input: &str) {
-    let total: u64 = parse_input(input)
+/// Alternative to `part1` that prunes partial results instead of trying every operator combination.
+fn part1_recurse(equations: &[(u64, Vec<u64>)]) -> Answer {
+    let total: u64 = equations
         .par_iter()
         .filter_map(|(target, components)| {
             compute_recurse(components, &[Op::Add, Op::Mul], *target)
         })
         .sum();
 
-    println!("(recursive) Day 7 Part 1: {}", total);
+    total.into()
 }
 
-fn part2(input: &str) {
+fn part2(equations: &[(u64, Vec<u64>)]) -> Answer {
     let cached_ops: DashMap<usize, Vec<Vec<Op>>> = DashMap::new();
-    let total: u64 = parse_input(input)
+    let total: u64 = equations
         .par_iter()
         .filter_map(|(target, components)| {
             let ops = cached_ops
@@ -124,23 +125,49 @@ fn part2(input: &str) {
             })
         })
         .sum();
-    println!("Day 7 Part 2: {}", total);
+    total.into()
 }
 
-fn part2_recurse(input: &str) {
-    let total: u64 = parse_input(input)
+/// Alternative to `part2` that prunes partial results instead of trying every operator combination.
+fn part2_recurse(equations: &[(u64, Vec<u64>)]) -> Answer {
+    let total: u64 = equations
         .par_iter()
         .filter_map(|(target, components)| {
             compute_recurse(components, &[Op::Add, Op::Mul, Op::Cat], *target)
         })
         .sum();
 
-    println!("(recursive) Day 7 Part 2: {}", total);
+    total.into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part1_recurse);
-    runner(part2);
-    runner(part2_recurse);
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Vec<(u64, Vec<u64>)>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
+
+pub const VARIANTS: &[Variant] = &[
+    Variant {
+        name: "recurse",
+        part: 1,
+        solve: |input| solve::<Day7>(input, |equations| Ok(part1_recurse(equations))),
+    },
+    Variant {
+        name: "recurse",
+        part: 2,
+        solve: |input| solve::<Day7>(input, |equations| Ok(part2_recurse(equations))),
+    },
+];
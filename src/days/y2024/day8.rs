@@ -1,10 +1,12 @@
 use std::iter::successors;
 
-use adventofcode_2024::{runner, utils::Loc};
+use crate::{utils::Loc, Answer, Solution};
 use hashbrown::{HashMap, HashSet};
 use itertools::Itertools;
 
-fn parse_input(input: &str) -> (HashMap<char, Vec<Loc>>, (usize, usize)) {
+type Antennas = HashMap<char, Vec<Loc>>;
+
+fn parse_input(input: &str) -> (Antennas, (usize, usize)) {
     let rows = input.lines().count();
     let cols = input.lines().next().unwrap().len();
 
@@ -27,8 +29,8 @@ fn parse_input(input: &str) -> (HashMap<char, Vec<Loc>>, (usize, usize)) {
     (antennas, (rows, cols))
 }
 
-fn part1(input: &str) {
-    let (antennas, bounds) = parse_input(input);
+fn part1((antennas, bounds): &(Antennas, (usize, usize))) -> Answer {
+    let bounds = *bounds;
 
     let mut antinodes: HashSet<Loc> = HashSet::new();
     antennas.iter().for_each(|(_, nodes)| {
@@ -43,11 +45,11 @@ fn part1(input: &str) {
         });
     });
 
-    println!("Day 8 Part 1: {}", antinodes.len());
+    antinodes.len().into()
 }
 
-fn part2(input: &str) {
-    let (antennas, bounds) = parse_input(input);
+fn part2((antennas, bounds): &(Antennas, (usize, usize))) -> Answer {
+    let bounds = *bounds;
 
     let mut antinodes: HashSet<Loc> = HashSet::new();
     antennas.iter().for_each(|(_, nodes)| {
@@ -62,10 +64,24 @@ fn part2(input: &str) {
         });
     });
 
-    println!("Day 8 Part 2: {}", antinodes.len());
+    antinodes.len().into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = (Antennas, (usize, usize));
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
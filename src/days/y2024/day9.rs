@@ -1,6 +1,6 @@
 use std::{cmp::min, collections::LinkedList};
 
-use adventofcode_2024::runner;
+use crate::{Answer, Solution};
 use hashbrown::HashSet;
 use itertools::Itertools;
 
@@ -11,7 +11,7 @@ enum SegmentType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Segment {
+pub struct Segment {
     len: i64,
     segment_type: SegmentType,
 }
@@ -100,8 +100,8 @@ fn parse_input(input: &str) -> LinkedList<Segment> {
     list
 }
 
-fn part1(input: &str) {
-    let mut list = parse_input(input);
+fn part1(list: &LinkedList<Segment>) -> Answer {
+    let mut list = list.clone();
     let mut new_list = LinkedList::new();
     new_list.push_back(list.pop_front().unwrap());
 
@@ -149,11 +149,11 @@ fn part1(input: &str) {
         }
     }
 
-    println!("Day 9 Part 1: {}", checksum(&new_list));
+    checksum(&new_list).into()
 }
 
-fn part2(input: &str) {
-    let mut vec = parse_input(input).into_iter().collect_vec();
+fn part2(list: &LinkedList<Segment>) -> Answer {
+    let mut vec = list.iter().copied().collect_vec();
     let file_ids: HashSet<i64> = vec
         .iter()
         .filter_map(|x| match x.segment_type {
@@ -211,10 +211,24 @@ fn part2(input: &str) {
         locked_ids.insert(unlocked_file.id().unwrap());
     }
 
-    println!("Day 9 Part 2: {}", checksum(&vec));
+    checksum(&vec).into()
 }
 
-fn main() {
-    runner(part1);
-    runner(part2);
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = LinkedList<Segment>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Input> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> anyhow::Result<Answer> {
+        Ok(part2(input))
+    }
 }
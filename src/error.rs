@@ -0,0 +1,187 @@
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard, Once};
+use std::time::Duration;
+
+use anyhow::anyhow;
+
+use crate::client::DownloadError;
+use crate::unlock::format_countdown;
+
+/// Everything that can stop a run, each with its own exit code so that scripts can tell them
+/// apart (clap uses 2 for bad arguments).
+#[derive(Debug)]
+pub enum Error {
+    /// There's no session token to download inputs or submit answers with.
+    NoSession,
+    /// The site doesn't accept the session token, most likely because it expired.
+    InvalidSession,
+    /// An input file is missing, or couldn't be read or written.
+    Input(anyhow::Error),
+    /// Talking to the site failed.
+    Network(anyhow::Error),
+    /// A solution's parsing step failed or panicked.
+    Parse(anyhow::Error),
+    /// A part failed or panicked.
+    Solution(anyhow::Error),
+    /// Parts got slower than the allowed threshold against a benchmark baseline.
+    Regression(usize),
+    /// The puzzle hasn't unlocked yet, so there's nothing to ask the site for.
+    Locked {
+        year: u16,
+        day: u8,
+        remaining: Duration,
+    },
+    Other(anyhow::Error),
+}
+
+impl Error {
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Other(_) => 1,
+            Error::NoSession | Error::InvalidSession => 3,
+            Error::Input(_) => 4,
+            Error::Network(_) => 5,
+            Error::Parse(_) => 6,
+            Error::Solution(_) => 7,
+            Error::Regression(_) => 8,
+            Error::Locked { .. } => 9,
+        }
+    }
+
+    /// Keeps an [`Error`] that was raised on the way as it is, turns a rejected session token into
+    /// [`Error::InvalidSession`], and treats anything else as a network failure.
+    pub fn network(err: anyhow::Error) -> Self {
+        match err.downcast::<Error>() {
+            Ok(err) => err,
+            Err(err) if err.downcast_ref() == Some(&DownloadError::LoggedOut) => {
+                Error::InvalidSession
+            }
+            Err(err) => Error::Network(err),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NoSession => write!(
+                f,
+                "No session token, log in with `cargo run -- login`, or set the AOC_SESSION environment variable or `session_file` in aoc.toml"
+            ),
+            Error::InvalidSession => write!(
+                f,
+                "The site doesn't accept the session token, it may have expired. Log in again with `cargo run -- login`"
+            ),
+            Error::Input(err) => write!(f, "Input error: {err:#}"),
+            Error::Network(err) => write!(f, "Network error: {err:#}"),
+            Error::Parse(err) => write!(f, "Parsing the input failed: {err:#}"),
+            Error::Solution(err) => write!(f, "The solution failed: {err:#}"),
+            Error::Regression(count) => write!(
+                f,
+                "{count} part(s) regressed by more than the threshold against the baseline"
+            ),
+            Error::Locked {
+                year,
+                day,
+                remaining,
+            } => write!(
+                f,
+                "Day {day} of {year} unlocks in {} (at {year}-12-{day:02} 05:00 UTC), pass --wait to wait for it",
+                format_countdown(*remaining)
+            ),
+            Error::Other(err) => write!(f, "{err:#}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+thread_local! {
+    /// How deep in [`catch_panic`] calls this thread is, and where the first panic caught on it
+    /// happened.
+    static CATCHING: Cell<usize> = const { Cell::new(0) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// How many [`catch_panic`] calls are running on any thread. Solutions that use rayon panic on
+/// its worker threads rather than the one that caught them, and those panics can't be traced back
+/// to a call, so they count as caught while any call is running.
+static CATCHING_ANYWHERE: AtomicUsize = AtomicUsize::new(0);
+/// Where the first panic on a rayon worker happened, for the call its panic reaches.
+static WORKER_PANIC_LOCATION: Mutex<Option<String>> = Mutex::new(None);
+
+/// Runs `f`, turning a panic into an error so that one crashing solution can be reported like
+/// any other failure. The panic isn't printed as well, so the error is all that shows.
+pub fn catch_panic<T>(f: impl FnOnce() -> T) -> anyhow::Result<T> {
+    quiet_caught_panics();
+    // a location left over from a panic that some other call caught mustn't end up in this one
+    if CATCHING.replace(CATCHING.get() + 1) == 0 {
+        PANIC_LOCATION.take();
+    }
+    if CATCHING_ANYWHERE.fetch_add(1, Ordering::SeqCst) == 0 {
+        worker_panic_location().take();
+    }
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING_ANYWHERE.fetch_sub(1, Ordering::SeqCst);
+    CATCHING.set(CATCHING.get() - 1);
+
+    result.map_err(|payload| {
+        let message = panic_message(payload.as_ref());
+        let location = PANIC_LOCATION
+            .take()
+            .or_else(|| worker_panic_location().take());
+        match location {
+            Some(location) => anyhow!("panicked at {location}: {message}"),
+            None => anyhow!("panicked: {message}"),
+        }
+    })
+}
+
+fn worker_panic_location() -> MutexGuard<'static, Option<String>> {
+    // the hook runs while panicking, so a poisoned lock still holds a usable location
+    WORKER_PANIC_LOCATION
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+/// Wraps the panic hook once, so that panics inside [`catch_panic`] only have their location
+/// noted, while every other panic is printed as usual. A panic is inside a call when it happens
+/// on the thread that made it, or on a rayon worker while any call is running. The hook is shared
+/// by all threads, so it is never swapped back and forth.
+fn quiet_caught_panics() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(ToString::to_string);
+            if CATCHING.get() > 0 {
+                PANIC_LOCATION.with_borrow_mut(|first| {
+                    if first.is_none() {
+                        *first = location;
+                    }
+                });
+            } else if rayon::current_thread_index().is_some()
+                && CATCHING_ANYWHERE.load(Ordering::SeqCst) > 0
+            {
+                let mut first = worker_panic_location();
+                if first.is_none() {
+                    *first = location;
+                }
+            } else {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
@@ -1,127 +1,1038 @@
-use clap::Parser;
-use std::env::VarError;
+use clap::error::ErrorKind;
+use clap::{Args, CommandFactory, Parser, Subcommand, ValueEnum};
+use std::any::Any;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::iter;
 use std::path::{Path, PathBuf};
-use std::time::Instant;
-use std::{env, fs};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
 
-use anyhow::Result;
-use reqwest::header::COOKIE;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
 
+use crate::answers::{Answers, Check};
+use crate::bench::{bench, Baseline, Stats, Verdict};
+use crate::client::{check_input, AocClient};
+use crate::config::Config;
+use crate::error::{catch_panic, Error};
+use crate::memory::AllocStats;
+use crate::puzzle::fetch_examples;
+
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod config;
+pub mod days;
+pub mod error;
+pub mod memory;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod puzzle;
+pub mod report;
+pub mod scaffold;
+pub mod session;
+pub mod store;
+pub mod submit;
+pub mod unlock;
 pub mod utils;
+pub mod watch;
+
+/// Prints a line of the human-readable output, which moves to stderr when stdout is taken by a
+/// `--format` report or by `--bench-json -`.
+#[macro_export]
+macro_rules! say {
+    ($opt:expr, $($arg:tt)*) => {
+        if $opt.stdout_taken() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
+
+#[cfg(feature = "alloc-stats")]
+#[global_allocator]
+static GLOBAL: memory::CountingAlloc = memory::CountingAlloc;
 
 #[derive(Parser)]
-struct Opt {
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    #[command(flatten)]
+    pub opt: Opt,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Create a new day from the template in `src/days/README.md`, and fetch its examples
+    New {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+
+        /// The puzzle year [default: `year` in aoc.toml]
+        #[arg(short, long, value_parser = clap::value_parser!(u16).range(2015..))]
+        year: Option<u16>,
+    },
+    /// Check a session token with the site, and save it for later runs
+    Login {
+        /// The `session` cookie from adventofcode.com [default: read from stdin]
+        token: Option<String>,
+    },
+    /// Show who the session token belongs to, which also checks that it's still valid
+    Whoami,
+}
+
+#[derive(Args)]
+pub struct Opt {
+    /// The day to run, or `all` to run every solved day
+    #[arg(required_unless_present = "days")]
+    pub day: Option<Selection>,
+
+    /// The days to run instead of a single one, e.g. `3,5-9`
+    #[arg(
+        long,
+        value_name = "DAYS",
+        value_delimiter = ',',
+        conflicts_with = "day"
+    )]
+    pub days: Vec<DayRange>,
+
+    /// The puzzle year [default: `year` in aoc.toml]
+    #[arg(short, long, value_parser = clap::value_parser!(u16).range(2015..))]
+    pub year: Option<u16>,
+
     #[arg(short, long)]
-    real: bool,
+    pub real: bool,
 
+    /// Run against the example input, even when `aoc.toml` defaults to the real one
+    #[arg(long, conflicts_with = "real")]
+    pub example: bool,
+
+    /// Run a single example, like `day3_small`, even when `aoc.toml` defaults to the real input
+    #[arg(short, long, conflicts_with = "real")]
+    pub alt: Option<String>,
+
+    /// Wait for the puzzle to unlock rather than refusing to download it early
+    #[arg(long)]
+    pub wait: bool,
+
+    /// Download the real input again, even when it's already saved. Needs --real
+    #[arg(long)]
+    pub refresh: bool,
+
+    /// Run against this file instead, or `-` to read stdin. Nothing is downloaded, and the answers
+    /// aren't checked
+    #[arg(
+        short,
+        long,
+        value_name = "PATH",
+        conflicts_with_all = ["real", "example", "alt", "fetch", "refresh", "wait", "save", "submit"]
+    )]
+    pub input: Option<PathBuf>,
+
+    /// Fetch the examples from the puzzle page, which only happens by default when they're missing
+    #[arg(long, conflicts_with = "real")]
+    pub fetch: bool,
+
+    /// Store this run's answers as the correct ones
+    #[arg(long)]
+    pub save: bool,
+
+    /// Run each part repeatedly and report timing statistics
     #[arg(short, long)]
-    alt: Option<String>,
+    pub bench: bool,
+
+    /// Run each part once, even when `aoc.toml` turns benchmarking on
+    #[arg(long, conflicts_with = "bench")]
+    pub no_bench: bool,
+
+    /// Also write the benchmark statistics as JSON to this file, or `-` for stdout, which moves the
+    /// usual output to stderr. Needs --bench
+    #[arg(long, value_name = "FILE")]
+    pub bench_json: Option<PathBuf>,
+
+    /// Save the benchmark results as the baseline with this name. Needs --bench
+    #[arg(long, value_name = "NAME")]
+    pub save_baseline: Option<String>,
+
+    /// Compare the benchmark results against the baseline with this name. Needs --bench
+    #[arg(long, value_name = "NAME")]
+    pub baseline: Option<String>,
+
+    /// How many percent slower than the baseline a part may get before it counts as regressed
+    /// [default: 5]
+    #[arg(long, value_name = "PERCENT")]
+    pub threshold: Option<f64>,
+
+    /// Rebuild and rerun the day whenever its source, `src/utils.rs` or its input changes
+    #[arg(short, long, conflicts_with_all = ["submit", "save", "format"])]
+    pub watch: bool,
+
+    /// How to report the results on stdout. Other formats move the usual output to stderr
+    #[arg(long, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
+
+    /// Submit this part's answer to the site. Needs --real
+    #[arg(long, value_name = "PART", value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub submit: Option<u8>,
+}
+
+impl Opt {
+    /// Fills in the year and the flags that `aoc.toml` turns on by default. `--fetch` and `--alt`
+    /// are about the examples, so like `--example` they keep `real` from being turned on, and
+    /// `--no-bench` does the same for `bench`.
+    pub fn apply_defaults(&mut self, config: &Config) {
+        let defaults = &config.defaults;
+        self.year = self.year.or(Some(config.year));
+        self.real |= defaults.real
+            && !self.example
+            && !self.fetch
+            && self.alt.is_none()
+            && self.input.is_none();
+        self.bench |= defaults.bench && !self.no_bench;
+        self.threshold = self.threshold.or(defaults.threshold);
+    }
+
+    /// Checks the flags that only make sense with `--real` or `--bench`, or that can't share
+    /// stdout with `--format`. This happens after [`Opt::apply_defaults`] rather than in clap, so
+    /// that `aoc.toml` turning those on counts.
+    pub fn check_requirements(&self) -> Result<(), clap::Error> {
+        let error = |kind, msg: String| Err(Cli::command().error(kind, msg));
+        let needs_real = [
+            ("--submit", self.submit.is_some()),
+            ("--refresh", self.refresh),
+        ];
+        let needs_bench = [
+            ("--bench-json", self.bench_json.is_some()),
+            ("--save-baseline", self.save_baseline.is_some()),
+            ("--baseline", self.baseline.is_some()),
+        ];
+
+        let missing = |flags: &[(&'static str, bool)], set: bool| {
+            flags
+                .iter()
+                .find(|&&(_, used)| used && !set)
+                .map(|&(flag, _)| flag)
+        };
+        if let Some(flag) = missing(&needs_real, self.real) {
+            return error(
+                ErrorKind::MissingRequiredArgument,
+                format!("{flag} needs --real, or `real = true` under [defaults] in aoc.toml"),
+            );
+        }
+        if let Some(flag) = missing(&needs_bench, self.bench) {
+            return error(
+                ErrorKind::MissingRequiredArgument,
+                format!("{flag} needs --bench, or `bench = true` under [defaults] in aoc.toml"),
+            );
+        }
+        if self.format != Format::Text && self.bench_json.as_deref() == Some(Path::new("-")) {
+            return error(
+                ErrorKind::ArgumentConflict,
+                "--bench-json - can't share stdout with a --format other than text".to_string(),
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Whether stdout is kept for machine-readable output, either a `--format` report or the
+    /// benchmark statistics of `--bench-json -`.
+    pub fn stdout_taken(&self) -> bool {
+        self.format != Format::Text || self.bench_json.as_deref() == Some(Path::new("-"))
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year.unwrap_or_else(|| config().year)
+    }
+
+    pub fn threshold(&self) -> f64 {
+        self.threshold.unwrap_or(DEFAULT_THRESHOLD)
+    }
+
+    /// The solved days to run, in day order. Days that a range covers but that aren't solved yet
+    /// are left out, while asking for a single unsolved day is an error.
+    pub fn selected_days(&self) -> Result<Vec<&'static Day>, Error> {
+        let year = self.year();
+        let solved = days::days(year);
+
+        let selected: Vec<_> = match (self.day, self.days.as_slice()) {
+            (Some(Selection::Day(n)), _) => vec![days::find(year, n).ok_or_else(|| {
+                Error::Other(anyhow!("Day {n} of {year} hasn't been solved yet"))
+            })?],
+            (Some(Selection::All), _) | (None, []) => solved.iter().collect(),
+            (None, ranges) => {
+                for range in ranges {
+                    if range.start == range.end && days::find(year, range.start).is_none() {
+                        return Err(Error::Other(anyhow!(
+                            "Day {} of {year} hasn't been solved yet",
+                            range.start
+                        )));
+                    }
+                }
+                solved
+                    .iter()
+                    .filter(|day| ranges.iter().any(|range| range.contains(day.day)))
+                    .collect()
+            }
+        };
+
+        if selected.is_empty() {
+            return Err(Error::Other(anyhow!(
+                "None of the selected days of {year} have been solved yet"
+            )));
+        }
+        Ok(selected)
+    }
+}
+
+const DEFAULT_THRESHOLD: f64 = 5.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// The usual output, and a summary table when more than one day or example ran
+    Text,
+    /// An array with an object per part
+    Json,
+    /// A row per part
+    Csv,
+    /// A test suite per day and a test case per part, for CI
+    Junit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Selection {
+    All,
+    Day(u8),
+}
+
+impl FromStr for Selection {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "all" => Ok(Selection::All),
+            _ => parse_day(s.trim_start_matches("day"))
+                .map(Selection::Day)
+                .map_err(|_| anyhow!("expected a day from 1 to 25 or `all`, got `{s}`")),
+        }
+    }
+}
+
+/// A single day (`3`) or an inclusive range of days (`5-9`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DayRange {
+    pub start: u8,
+    pub end: u8,
+}
+
+impl DayRange {
+    pub fn contains(&self, day: u8) -> bool {
+        (self.start..=self.end).contains(&day)
+    }
+}
+
+impl FromStr for DayRange {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let (start, end) = match s.split_once('-') {
+            Some((start, end)) => (parse_day(start)?, parse_day(end)?),
+            None => (parse_day(s)?, parse_day(s)?),
+        };
+        if start > end {
+            bail!("`{s}` is an empty range");
+        }
+
+        Ok(DayRange { start, end })
+    }
+}
+
+/// A day of the calendar, which only goes up to 25.
+fn parse_day(day: &str) -> Result<u8> {
+    day.trim()
+        .parse::<u8>()
+        .ok()
+        .filter(|day| (1..=25).contains(day))
+        .ok_or_else(|| anyhow!("expected a day from 1 to 25, got `{day}`"))
+}
+
+/// A puzzle answer, as returned by either part of a [`Solution`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    Str(String),
+    #[serde(skip)]
+    Unimplemented,
+}
+
+impl std::fmt::Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{n}"),
+            Answer::Str(s) => write!(f, "{s}"),
+            Answer::Unimplemented => write!(f, "not implemented"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(val: $t) -> Self {
+                // anything too big for an i64 is still a valid answer, just not a number we can compare
+                i64::try_from(val).map_or_else(|_| Answer::Str(val.to_string()), Answer::Int)
+            }
+        })*
+    };
+}
+
+answer_from_int!(i32, i64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(val: String) -> Self {
+        Answer::Str(val)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(val: &str) -> Self {
+        Answer::Str(val.to_string())
+    }
+}
+
+/// A day's solution. The input is parsed once, and each part returns its answer instead of
+/// printing it, so that the runner can format, time and check it.
+pub trait Solution {
+    const DAY: u8;
+    type Input: 'static;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Answer>;
+
+    fn part2(_input: &Self::Input) -> Result<Answer> {
+        Ok(Answer::Unimplemented)
+    }
+}
+
+/// A day's parsed input. Its type depends on the day, so it's only known to that day's parts.
+pub type Parsed = Box<dyn Any>;
+
+pub type ParseFn = fn(&str) -> Result<Parsed, Error>;
+
+/// One part of a day, run against the output of the day's [`ParseFn`].
+pub type PartFn = fn(&Parsed) -> Result<Answer, Error>;
+
+/// A registered day: its number, its parsing step, both of its parts, and any other ways of
+/// solving them.
+pub struct Day {
+    pub day: u8,
+    pub parse: ParseFn,
+    pub parts: [PartFn; 2],
+    pub variants: &'static [Variant],
+}
+
+impl Day {
+    pub const fn new<S: Solution>() -> Self {
+        Day {
+            day: S::DAY,
+            parse: parse::<S>,
+            parts: [solve_part1::<S>, solve_part2::<S>],
+            variants: &[],
+        }
+    }
+
+    /// Registers other solutions to the day's parts, which run, and are timed and checked, right
+    /// after the part they solve.
+    pub const fn with_variants(self, variants: &'static [Variant]) -> Self {
+        Day { variants, ..self }
+    }
+
+    /// The variants that solve `part`, in the order they were registered.
+    pub fn variants_of(&self, part: u8) -> impl Iterator<Item = &Variant> {
+        self.variants
+            .iter()
+            .filter(move |variant| variant.part == part)
+    }
 }
 
-pub fn is_real() -> bool {
-    let opt = Opt::parse();
-    opt.real
+/// Another way of solving one of a day's parts, such as a faster algorithm kept next to the
+/// original to compare against. Its `solve` usually wraps a function with [`solve`].
+pub struct Variant {
+    pub name: &'static str,
+    pub part: u8,
+    pub solve: PartFn,
 }
 
-pub fn runner(f: impl Fn(&str)) {
-    let opt = Opt::parse();
+// Panics are caught, so that they're reported like errors from the step they happened in.
 
-    let input = get_input(&opt);
+fn parse<S: Solution>(input: &str) -> Result<Parsed, Error> {
+    catch_panic(|| S::parse(input))
+        .and_then(|parsed| parsed)
+        .map(|parsed| Box::new(parsed) as Parsed)
+        .map_err(Error::Parse)
+}
+
+fn solve_part1<S: Solution>(input: &Parsed) -> Result<Answer, Error> {
+    solve::<S>(input, S::part1)
+}
+
+fn solve_part2<S: Solution>(input: &Parsed) -> Result<Answer, Error> {
+    solve::<S>(input, S::part2)
+}
+
+/// Runs one of a [`Solution`]'s parts, or a function that solves one like a [`Variant`], against
+/// the day's parsed input.
+pub fn solve<S: Solution>(
+    input: &Parsed,
+    part: fn(&S::Input) -> Result<Answer>,
+) -> Result<Answer, Error> {
+    let input = input
+        .downcast_ref::<S::Input>()
+        .expect("parts are only run against their own day's parsed input");
+    catch_panic(|| part(input))
+        .and_then(|answer| answer)
+        .map_err(Error::Solution)
+}
+
+/// The outcome of running a day against one input: how long parsing took, and the parts that ran.
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub year: u16,
+    pub day: u8,
+    /// The name of the input file the day ran against.
+    pub input: String,
+    /// The single run's time, or the median when benchmarking.
+    pub parse: Duration,
+    pub parse_stats: Option<Stats>,
+    /// Only measured with the `alloc-stats` feature.
+    pub parse_alloc: Option<AllocStats>,
+    pub parts: Vec<PartReport>,
+}
+
+impl DayReport {
+    /// Parsing and the parts themselves. Variants are alternatives to the parts rather than more
+    /// work, so only their own rows show their time.
+    pub fn total(&self) -> Duration {
+        self.parse
+            + self
+                .parts
+                .iter()
+                .filter(|part| part.variant.is_none())
+                .map(|part| part.duration)
+                .sum::<Duration>()
+    }
+}
+
+/// A day that stopped with an error while running several days, so that the rest still ran.
+#[derive(Debug, Clone)]
+pub struct FailedDay {
+    pub year: u16,
+    pub day: u8,
+    pub error: String,
+}
+
+/// The outcome of running one part of a day.
+#[derive(Debug, Clone)]
+pub struct PartReport {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// The [`Variant`] that solved the part, if it wasn't the part itself.
+    pub variant: Option<&'static str>,
+    /// The name of the input file the part ran against.
+    pub input: String,
+    pub answer: Answer,
+    pub check: Check,
+    /// The single run's time, or the median when benchmarking. Parsing isn't included.
+    pub duration: Duration,
+    pub stats: Option<Stats>,
+    /// Only measured with the `alloc-stats` feature.
+    pub alloc: Option<AllocStats>,
+    pub verdict: Option<Verdict>,
+}
+
+impl PartReport {
+    /// The part's number, followed by the variant's name for variants: `1`, `2 (recurse)`.
+    pub fn label(&self) -> String {
+        match self.variant {
+            Some(variant) => format!("{} ({variant})", self.part),
+            None => self.part.to_string(),
+        }
+    }
+}
+
+/// Runs a day against each of its inputs: the one picked by `--real`, `--alt` or `--input`, or
+/// otherwise every example, each with the parts it's meant for.
+pub fn run(day: &Day, opt: &Opt) -> Result<Vec<DayReport>, Error> {
+    let year = opt.year();
+    let inputs = match &opt.input {
+        Some(path) => vec![(
+            InputFile::both_parts(custom_input_name(path)),
+            read_input(path)?,
+        )],
+        None => {
+            // only the site cares about the unlock, so runs that don't ask it for anything go ahead
+            if opt.wait && (opt.submit.is_some() || will_download(year, day.day, opt)) {
+                unlock::wait_for_unlock(year, day.day);
+            }
+            get_inputs(year, day.day, opt)?
+        }
+    };
+
+    let answers_path = make_answers_path(year, opt.real);
+    let mut answers = match opt.input {
+        // there's nothing to check a custom input's answers against
+        Some(_) => Answers::default(),
+        None => Answers::load(&answers_path).map_err(Error::Input)?,
+    };
+
+    let baseline = opt.baseline.as_deref().map(load_baseline).transpose()?;
+    let kind = match (&opt.input, opt.real) {
+        (Some(_), _) => "input",
+        (None, true) => "real",
+        (None, false) => "example",
+    };
+
+    let mut reports = Vec::with_capacity(inputs.len());
+    for (file, input) in inputs {
+        let name = file.name;
+        say!(opt, "---");
+        say!(opt, "Day {} Input: {name}", day.day);
+        let parse = time(day.parse, input.as_str(), opt.bench)?;
+        say!(opt, "Day {} Parse: {parse}", day.day);
+        let parsed = parse.output;
+
+        let mut parts = Vec::with_capacity(file.parts.len());
+        for part in file.parts {
+            let steps = iter::once((None, day.parts[part as usize - 1]))
+                .chain(day.variants_of(part).map(|v| (Some(v.name), v.solve)));
+            for (variant, solve) in steps {
+                say!(opt, "---");
+                let timed = time(solve, &parsed, opt.bench)?;
+                let timing = timed.to_string();
+                let Timed {
+                    output: answer,
+                    duration,
+                    stats,
+                    alloc,
+                } = timed;
+
+                let mut report = PartReport {
+                    year,
+                    day: day.day,
+                    part,
+                    variant,
+                    input: name.clone(),
+                    check: answers.check(&name, part, &answer),
+                    answer,
+                    duration,
+                    stats,
+                    alloc,
+                    verdict: None,
+                };
+                let label = report.label();
+                match report.answer {
+                    Answer::Unimplemented => {
+                        say!(opt, "Day {} Part {label}: {}", day.day, report.answer)
+                    }
+                    _ => say!(
+                        opt,
+                        "Day {} Part {label}: {} [{}]",
+                        day.day,
+                        report.answer,
+                        report.check
+                    ),
+                }
+                say!(opt, "--- {timing}");
 
-    println!("---");
+                report.verdict = baseline.as_ref().zip(stats).map(|(baseline, stats)| {
+                    baseline.compare(kind, &report, &stats, opt.threshold())
+                });
+                if let Some(verdict) = report.verdict {
+                    say!(opt, "--- {verdict}");
+                }
+
+                parts.push(report);
+            }
+        }
+
+        let report = DayReport {
+            year,
+            day: day.day,
+            input: name,
+            parse: parse.duration,
+            parse_stats: parse.stats,
+            parse_alloc: parse.alloc,
+            parts,
+        };
+        let total = report.total();
+        let share = |duration: Duration| {
+            100.0 * duration.as_secs_f64() / total.as_secs_f64().max(f64::MIN_POSITIVE)
+        };
+        let shares: Vec<_> = iter::once(format!("parse {:.1}%", share(report.parse)))
+            .chain(
+                report
+                    .parts
+                    .iter()
+                    .filter(|part| part.variant.is_none())
+                    .map(|part| format!("part {} {:.1}%", part.part, share(part.duration))),
+            )
+            .collect();
+        say!(opt, "---");
+        say!(
+            opt,
+            "Day {} Total: {total:?} ({})",
+            day.day,
+            shares.join(" | ")
+        );
+
+        reports.push(report);
+    }
+
+    if opt.save {
+        // a variant's answer is only ever checked against its part's
+        let parts = reports.iter().flat_map(|report| &report.parts);
+        for part in parts.filter(|part| part.variant.is_none()) {
+            answers.set(&part.input, part.part, part.answer.clone());
+        }
+        answers.save(&answers_path).map_err(Error::Input)?;
+    }
+
+    if let Some(baseline_name) = &opt.save_baseline {
+        let path = make_baseline_path(baseline_name);
+        let mut baseline = Baseline::load_or_default(&path).map_err(Error::Other)?;
+        baseline.record(kind, &reports);
+        baseline.save(&path).map_err(Error::Other)?;
+    }
+
+    Ok(reports)
+}
+
+/// The output of one step of a day, and what it took to produce.
+struct Timed<T> {
+    output: T,
+    /// The single run's time, or the median when benchmarking.
+    duration: Duration,
+    stats: Option<Stats>,
+    alloc: Option<AllocStats>,
+}
+
+impl<T> fmt::Display for Timed<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.stats {
+            Some(stats) => write!(f, "{stats}")?,
+            None => write!(f, "{:?}", self.duration)?,
+        }
+        if let Some(alloc) = self.alloc {
+            write!(f, " | {alloc}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs one step once, or benchmarks it. Allocations are counted over a single run, which
+/// doubles as part of the warmup when benchmarking.
+fn time<I: ?Sized, T>(
+    f: impl Fn(&I) -> Result<T, Error>,
+    input: &I,
+    bench_it: bool,
+) -> Result<Timed<T>, Error> {
+    let (result, alloc) = memory::track(|| runner(&f, input));
+    let (output, duration) = result?;
+    if !bench_it {
+        return Ok(Timed {
+            output,
+            duration,
+            stats: None,
+            alloc,
+        });
+    }
+
+    drop(output);
+    let (output, stats) = bench(f, input)?;
+    Ok(Timed {
+        output,
+        duration: stats.median,
+        stats: Some(stats),
+        alloc,
+    })
+}
+
+/// Times a single run of `f`. Works with anything that returns a `Result`, be it the steps of a
+/// [`Day`] or a day's own `anyhow::Result` functions.
+pub fn runner<I: ?Sized, T, E>(
+    f: impl Fn(&I) -> Result<T, E>,
+    input: &I,
+) -> Result<(T, Duration), E> {
     let start = Instant::now();
-    f(&input);
+    let output = f(input)?;
     let duration = start.elapsed();
-    println!("--- {duration:?}")
+
+    Ok((output, duration))
+}
+
+/// An input file, by name without extension, and the parts that run against it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputFile {
+    pub name: String,
+    pub parts: Vec<u8>,
+}
+
+impl InputFile {
+    pub fn both_parts(name: impl Into<String>) -> Self {
+        InputFile {
+            name: name.into(),
+            parts: vec![1, 2],
+        }
+    }
 }
 
-pub fn runner100(f: impl Fn(&str)) {
-    let opt = Opt::parse();
+/// Matches a day's example files to the parts they're for. Examples are named after the day, like
+/// `day3` or `day3_small`, and a `_partP` suffix (`day3_part2`) makes the file part P's own. A
+/// part that has examples of its own only runs on those, and otherwise runs on all the others.
+pub fn example_files<S: AsRef<str>>(day: u8, names: &[S]) -> Vec<InputFile> {
+    let prefix = format!("day{day}");
+    let mut names: Vec<&str> = names
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|name| {
+            name.strip_prefix(&prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('_'))
+        })
+        .collect();
+    names.sort_unstable();
 
-    let input = get_input(&opt);
+    let part_of = |name: &str| {
+        let rest = name.strip_prefix(&prefix)?.strip_prefix("_part")?;
+        let (part, rest) = rest.split_at_checked(1)?;
+        let part = part.parse().ok().filter(|part| (1..=2).contains(part))?;
+        (rest.is_empty() || rest.starts_with('_')).then_some(part)
+    };
+    let has_own = |part| names.iter().any(|&name| part_of(name) == Some(part));
 
-    println!("---");
-    let min_duration = (0..100)
-        .map(|_| {
-            let start = Instant::now();
-            f(&input);
-            start.elapsed()
+    names
+        .iter()
+        .filter_map(|&name| {
+            let parts: Vec<u8> = match part_of(name) {
+                Some(part) => vec![part],
+                None => [1, 2].into_iter().filter(|&part| !has_own(part)).collect(),
+            };
+            (!parts.is_empty()).then(|| InputFile {
+                name: name.to_string(),
+                parts,
+            })
         })
-        .min()
-        .unwrap();
+        .collect()
+}
 
-    println!("--- {min_duration:?}")
+/// The input files a day runs against. For the examples, that's every one of the day's that's
+/// on disk.
+pub(crate) fn input_files(year: u16, day: u8, opt: &Opt) -> Vec<InputFile> {
+    match (opt.real, &opt.alt) {
+        (false, Some(alt)) => vec![InputFile::both_parts(alt.as_str())],
+        (false, None) => {
+            let names: Vec<String> = fs::read_dir(make_inputs_dir(year, false))
+                .into_iter()
+                .flatten()
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect();
+            example_files(day, &names)
+        }
+        (true, _) => vec![InputFile::both_parts(format!("day{day}"))],
+    }
 }
 
-/// This function panics when something goes wrong. That is intended behaviour.
-fn get_input(opt: &Opt) -> String {
-    let bin = binary_name();
-    let day = bin
-        .strip_prefix("day")
-        .and_then(|b| b.parse::<u8>().ok())
-        .unwrap();
+/// What a custom input is called in reports: its file name, or `stdin`.
+fn custom_input_name(path: &Path) -> String {
+    match path.file_stem() {
+        Some(_) if path == Path::new("-") => "stdin".to_string(),
+        Some(stem) => stem.to_string_lossy().into_owned(),
+        None => path.display().to_string(),
+    }
+}
 
-    let path = make_path(&bin, opt);
-    match (path.exists(), opt.real) {
-        (true, _) => fs::read_to_string(path).map_err(anyhow::Error::from),
-        (false, false) => panic!("Oh no! I couldn't find the example input file :("),
-        (false, true) => download_and_save(path, day),
+/// Reads an input given with `--input`, where `-` stands for stdin.
+fn read_input(path: &Path) -> Result<String, Error> {
+    if path == Path::new("-") {
+        return io::read_to_string(io::stdin())
+            .context("Couldn't read the input from stdin")
+            .map_err(Error::Input);
     }
-    .unwrap()
+
+    fs::read_to_string(path)
+        .with_context(|| format!("Couldn't read {}", path.display()))
+        .map_err(Error::Input)
 }
 
-fn binary_name() -> String {
-    env::args()
-        .next()
-        .as_ref()
-        .map(Path::new)
-        .and_then(Path::file_name)
-        .and_then(std::ffi::OsStr::to_str)
-        .map(String::from)
-        .expect("Couldn't find the binary name for some reason...")
+/// Whether getting the day's inputs asks the site for anything: the real input when it isn't saved
+/// yet or `--refresh` is passed, or the examples when there are none yet or `--fetch` is passed.
+fn will_download(year: u16, day: u8, opt: &Opt) -> bool {
+    let exists = |file: &InputFile| make_path(&file.name, year, opt.real).exists();
+    let files = input_files(year, day, opt);
+    if opt.real {
+        opt.refresh || !files.iter().all(exists)
+    } else {
+        opt.fetch || !files.iter().any(exists)
+    }
 }
 
-fn make_path(bin_name: &str, opt: &Opt) -> PathBuf {
-    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+/// Reads the day's inputs, fetching the examples when they're missing and downloading the real
+/// input when it hasn't been yet.
+fn get_inputs(year: u16, day: u8, opt: &Opt) -> Result<Vec<(InputFile, String)>, Error> {
+    let mut files = input_files(year, day, opt);
+    if !opt.real && will_download(year, day, opt) {
+        if let Err(err) = fetch_examples(year, day) {
+            eprintln!("Couldn't fetch the examples for day {day}: {err:#}");
+        }
+        files = input_files(year, day, opt);
+    }
+    if files.is_empty() {
+        return Err(Error::Input(anyhow!(
+            "Couldn't find any example input for day {day} in {}",
+            make_inputs_dir(year, false).display()
+        )));
+    }
 
-    path.push("inputs");
-    path.push(if opt.real { "real" } else { "example" });
-    path.push(if opt.real || opt.alt.is_none() {
-        bin_name
-    } else {
-        opt.alt.as_ref().unwrap()
-    });
+    files
+        .into_iter()
+        .map(|file| {
+            let path = make_path(&file.name, year, opt.real);
+            let cached = if path.exists() && !opt.refresh {
+                let input = fs::read_to_string(&path)
+                    .with_context(|| format!("Couldn't read {}", path.display()))
+                    .map_err(Error::Input)?;
+                Some(input)
+            } else {
+                None
+            };
+
+            let input = match (cached, opt.real) {
+                (Some(input), false) => input,
+                (None, false) => {
+                    return Err(Error::Input(anyhow!(
+                        "Couldn't find the example input file {}",
+                        path.display()
+                    )))
+                }
+                // older versions saved whatever the site sent back, refusals included
+                (Some(input), true) => match check_input(200, &input) {
+                    Ok(()) => input,
+                    Err(err) => {
+                        eprintln!(
+                            "{} isn't an input ({err}), downloading it again",
+                            path.display()
+                        );
+                        download_and_save(&path, year, day)?
+                    }
+                },
+                (None, true) => download_and_save(&path, year, day)?,
+            };
+            Ok((file, input))
+        })
+        .collect()
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The project's `aoc.toml`, loaded on first use. A malformed file is reported and ignored here,
+/// so call [`load_config`] first to treat it as an error instead.
+pub fn config() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|err| {
+            eprintln!("Ignoring the config file: {err:#}");
+            Config::default()
+        })
+    })
+}
+
+pub fn load_config() -> Result<&'static Config, Error> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config);
+    }
+
+    let config = Config::load().map_err(Error::Other)?;
+    Ok(CONFIG.get_or_init(|| config))
+}
+
+/// Inputs are kept per year: `inputs/<year>/{real,example}`.
+fn make_inputs_dir(year: u16, real: bool) -> PathBuf {
+    let mut path = config().inputs_dir();
+
+    path.push(year.to_string());
+    path.push(if real { "real" } else { "example" });
+
+    path
+}
+
+pub(crate) fn make_path(name: &str, year: u16, real: bool) -> PathBuf {
+    let mut path = make_inputs_dir(year, real);
+
+    path.push(name);
     path.set_extension("txt");
 
     path
 }
 
-/// This function may cause a path error when the "inputs/real" directory doesn't exist.
-/// The workaround is to create the directory manually.
-fn download_and_save(path: PathBuf, day: u8) -> Result<String> {
-    let resp = download_input(2024, day)?;
-    fs::write(path, resp.as_bytes())?;
-    Ok(resp)
+pub(crate) fn make_answers_path(year: u16, real: bool) -> PathBuf {
+    let mut path = make_inputs_dir(year, real);
+
+    path.push("answers.toml");
+
+    path
+}
+
+/// Local, machine-specific state that isn't checked in, like benchmark baselines.
+pub(crate) fn make_state_dir() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push(".aoc");
+
+    path
 }
 
-fn download_input(year: u16, day: u8) -> Result<String> {
-    let client = reqwest::blocking::Client::new();
-    let resp = client
-        .get(make_url(year, day))
-        .header(
-            COOKIE,
-            String::from("session=") + get_session_token()?.as_str(),
-        )
-        .send()?
-        .text()?;
+fn make_baseline_path(name: &str) -> PathBuf {
+    let mut path = make_state_dir();
 
-    Ok(resp)
+    path.push("baselines");
+    path.push(name);
+    path.set_extension("json");
+
+    path
 }
 
-fn make_url(year: u16, day: u8) -> String {
-    format!("https://adventofcode.com/{year}/day/{day}/input")
+/// Reads the baseline that `--baseline` names. Unlike saving one, it has to exist, so that a typo
+/// in the name can't hide a regression.
+pub fn load_baseline(name: &str) -> Result<Baseline, Error> {
+    Baseline::load(&make_baseline_path(name)).map_err(Error::Other)
 }
 
-fn get_session_token() -> Result<String, VarError> {
-    env::var("AOC_SESSION")
+/// Creates the inputs directory when it doesn't exist yet. Nothing is saved when the download
+/// fails, so a refusal from the site never ends up in place of the input.
+fn download_and_save(path: &Path, year: u16, day: u8) -> Result<String, Error> {
+    let resp = AocClient::shared()
+        .download_input(year, day)
+        .with_context(|| format!("Couldn't download the input of {year} day {day}"))
+        .map_err(Error::network)?;
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Couldn't create {}", dir.display()))
+            .map_err(Error::Input)?;
+    }
+    fs::write(path, resp.as_bytes())
+        .with_context(|| format!("Couldn't save the input to {}", path.display()))
+        .map_err(Error::Input)?;
+
+    Ok(resp)
 }
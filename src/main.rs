@@ -1,4 +1,135 @@
+use adventofcode_2024::{
+    bench::{self, Verdict},
+    error::Error,
+    load_baseline, load_config, report, run, say, scaffold, session, submit, watch, Cli, Command,
+    FailedDay, Format, Opt, Selection,
+};
+use anyhow::anyhow;
+use clap::Parser;
+
 fn main() {
-    println!("Hi, I do nothing right now.");
-    println!("Please run `cargo run --bin <day>` to run a specific day.");
+    let cli = Cli::parse();
+
+    let result = load_config().and_then(|config| match cli.command {
+        Some(Command::New { day, year }) => {
+            scaffold::new_day(year.unwrap_or(config.year), day).map_err(Error::Other)
+        }
+        Some(Command::Login { token }) => session::login(token),
+        Some(Command::Whoami) => session::whoami(),
+        None => {
+            let mut opt = cli.opt;
+            opt.apply_defaults(config);
+            if let Err(err) = opt.check_requirements() {
+                err.exit();
+            }
+            run_days(&opt)
+        }
+    });
+
+    if let Err(err) = result {
+        eprintln!("Error: {err}");
+        std::process::exit(err.exit_code());
+    }
+}
+
+fn run_days(opt: &Opt) -> Result<(), Error> {
+    let single_day = matches!(opt.day, Some(Selection::Day(_)));
+    if opt.submit.is_some() && !single_day {
+        return Err(Error::Other(anyhow!(
+            "Pick a single day to submit an answer for"
+        )));
+    }
+    if opt.input.is_some() && !single_day {
+        return Err(Error::Other(anyhow!(
+            "Pick a single day to run the input through"
+        )));
+    }
+
+    if opt.watch && !watch::is_child() {
+        let [day] = opt.selected_days()?[..] else {
+            return Err(Error::Other(anyhow!("Pick a single day to watch")));
+        };
+        return watch::watch(opt.year(), day.day, opt).map_err(Error::Other);
+    }
+
+    // a missing baseline would otherwise fail every day in turn
+    if let Some(name) = &opt.baseline {
+        load_baseline(name)?;
+    }
+
+    // with several days, one failing day shouldn't keep the rest from running, and even a single
+    // failing day still gets its error record in the --format report
+    let mut reports = vec![];
+    let mut failed = vec![];
+    let mut first_error = None;
+    for day in opt.selected_days()? {
+        match run(day, opt) {
+            Ok(day_reports) => reports.extend(day_reports),
+            Err(err) => {
+                if !single_day {
+                    eprintln!("Day {} failed: {err}", day.day);
+                }
+                failed.push(FailedDay {
+                    year: opt.year(),
+                    day: day.day,
+                    error: err.to_string(),
+                });
+                first_error.get_or_insert(err);
+            }
+        }
+    }
+
+    match opt.format {
+        Format::Text if !single_day || reports.len() > 1 => {
+            say!(
+                opt,
+                "\n{}",
+                report::summary_table(&reports, &failed).trim_end()
+            );
+        }
+        Format::Text => {}
+        Format::Json => println!(
+            "{}",
+            report::to_json(&reports, &failed).map_err(Error::Other)?
+        ),
+        Format::Csv => print!("{}", report::to_csv(&reports, &failed)),
+        Format::Junit => print!("{}", report::to_junit(&reports, &failed)),
+    }
+
+    let parts: Vec<_> = reports
+        .iter()
+        .flat_map(|report| report.parts.clone())
+        .collect();
+
+    if let Some(path) = &opt.bench_json {
+        bench::write_json(path, &reports).map_err(Error::Other)?;
+    }
+
+    if let Some(err) = first_error {
+        return Err(err);
+    }
+
+    if let Some(part) = opt.submit {
+        let report = parts
+            .iter()
+            .find(|report| report.part == part && report.variant.is_none())
+            .ok_or_else(|| Error::Other(anyhow!("Part {part} didn't run")))?;
+        let outcome = submit::submit(report)?;
+        say!(
+            opt,
+            "Submitted {} for day {} part {part}: {outcome}",
+            report.answer,
+            report.day
+        );
+    }
+
+    let regressions = parts
+        .iter()
+        .filter(|report| matches!(report.verdict, Some(Verdict::Regressed(_))))
+        .count();
+    if regressions > 0 {
+        return Err(Error::Regression(regressions));
+    }
+
+    Ok(())
 }
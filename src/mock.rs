@@ -0,0 +1,248 @@
+//! A tiny stand-in for the Advent of Code website, so that the network code can be tested without
+//! internet access (or a real session token). Point an [`AocClient`](crate::client::AocClient) at
+//! [`MockServer::url`] to use it.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Instant;
+
+use anyhow::{anyhow, Result};
+
+/// What the real site answers when there's no (valid) session cookie on an input request.
+pub const LOGGED_OUT_INPUT: &str =
+    "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n";
+
+/// What the real site answers when an input is requested before the puzzle unlocks.
+pub const NOT_UNLOCKED_INPUT: &str = "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n";
+
+/// Roughly what the real site shows instead of a page that needs a session.
+pub const LOGGED_OUT_PAGE: &str = "<header><div><h1 class=\"title-global\"><a href=\"/\">Advent of Code</a></h1><nav><ul><li><a href=\"/2024/auth/login\">[Log In]</a></li></ul></nav></div></header>";
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// When the request line arrived, to check how far apart the client spaced its requests.
+    pub received: Instant,
+}
+
+impl Request {
+    /// Header names are matched case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+#[derive(Default)]
+struct State {
+    session: Option<String>,
+    routes: HashMap<(String, String), Response>,
+    requests: Vec<Request>,
+}
+
+/// Serves canned responses on a random local port, on a background thread, until dropped.
+pub struct MockServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<State>>,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    pub fn start() -> Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(State::default()));
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let (thread_state, thread_shutdown) = (state.clone(), shutdown.clone());
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if thread_shutdown.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Ok(stream) = stream {
+                    // a broken connection only affects the request that was on it
+                    let _ = handle(stream, &thread_state);
+                }
+            }
+        });
+
+        Ok(MockServer {
+            addr,
+            state,
+            shutdown,
+        })
+    }
+
+    /// The base URL to hand to a client.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// Only accept input downloads and answer submissions that carry this session token.
+    pub fn require_session(&self, token: &str) {
+        self.state.lock().unwrap().session = Some(token.to_string());
+    }
+
+    pub fn respond(&self, method: &str, path: &str, status: u16, body: &str) {
+        self.state.lock().unwrap().routes.insert(
+            (method.to_string(), path.to_string()),
+            Response {
+                status,
+                body: body.to_string(),
+            },
+        );
+    }
+
+    pub fn input(&self, year: u16, day: u8, body: &str) {
+        self.respond("GET", &format!("/{year}/day/{day}/input"), 200, body);
+    }
+
+    pub fn puzzle(&self, year: u16, day: u8, html: &str) {
+        self.respond("GET", &format!("/{year}/day/{day}"), 200, html);
+    }
+
+    /// The settings page, showing that `name` is logged in.
+    pub fn user(&self, year: u16, name: &str) {
+        let html = format!(
+            "<header><div class=\"user\">{name} <span class=\"star-count\">12*</span></div></header>"
+        );
+        self.respond("GET", &format!("/{year}/settings"), 200, &html);
+    }
+
+    /// The page returned for every answer submitted to this day.
+    pub fn answer(&self, year: u16, day: u8, html: &str) {
+        self.respond("POST", &format!("/{year}/day/{day}/answer"), 200, html);
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        // wake the listener up, so that it notices it should stop
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+fn handle(mut stream: TcpStream, state: &Mutex<State>) -> Result<()> {
+    let request = read_request(&mut stream)?;
+    let response = route(&request, &state.lock().unwrap());
+    state.lock().unwrap().requests.push(request);
+
+    let reason = match response.status {
+        200 => "OK",
+        302 => "Found",
+        400 => "Bad Request",
+        404 => "Not Found",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    )?;
+
+    Ok(())
+}
+
+fn read_request(stream: &mut TcpStream) -> Result<Request> {
+    let mut reader = BufReader::new(stream);
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let received = Instant::now();
+    let mut parts = line.split_whitespace();
+    let (method, path) = parts
+        .next()
+        .zip(parts.next())
+        .ok_or_else(|| anyhow!("malformed request line: {line}"))?;
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut headers = vec![];
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((key, value)) = line.split_once(':') {
+            headers.push((key.trim().to_string(), value.trim().to_string()));
+        }
+    }
+
+    let mut request = Request {
+        method,
+        path,
+        headers,
+        body: String::new(),
+        received,
+    };
+    let len = request
+        .header("Content-Length")
+        .and_then(|len| len.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body)?;
+    request.body = String::from_utf8_lossy(&body).into_owned();
+
+    Ok(request)
+}
+
+fn route(request: &Request, state: &State) -> Response {
+    let needs_session = ["/input", "/answer", "/settings"]
+        .iter()
+        .any(|suffix| request.path.ends_with(suffix));
+    if let (true, Some(session)) = (needs_session, &state.session) {
+        let cookie = format!("session={session}");
+        if request.header("Cookie") != Some(cookie.as_str()) {
+            // the real site redirects pages to its login page
+            let (status, body) = if request.path.ends_with("/settings") {
+                (200, LOGGED_OUT_PAGE)
+            } else {
+                (400, LOGGED_OUT_INPUT)
+            };
+            return Response {
+                status,
+                body: body.to_string(),
+            };
+        }
+    }
+
+    let key = (request.method.clone(), request.path.clone());
+    match state.routes.get(&key) {
+        Some(response) => response.clone(),
+        None if request.path.ends_with("/input") => Response {
+            status: 404,
+            body: NOT_UNLOCKED_INPUT.to_string(),
+        },
+        None => Response {
+            status: 404,
+            body: "404 Not Found".to_string(),
+        },
+    }
+}
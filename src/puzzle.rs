@@ -0,0 +1,117 @@
+use std::fs;
+
+use anyhow::{bail, Result};
+use regex::Regex;
+
+use crate::answers::Answers;
+use crate::client::AocClient;
+use crate::{make_answers_path, make_path, Answer};
+
+/// An example input found on a puzzle page, along with the answers the page gives for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    /// The input file name without extension, e.g. `day3` or `day3_part2`.
+    pub name: String,
+    pub input: String,
+    /// The answers for part 1 and part 2, as far as the page gives them.
+    pub answers: [Option<Answer>; 2],
+}
+
+/// Pulls the examples out of a puzzle page. Each part's description is its own `<article>`; the
+/// first `<pre><code>` block in it is taken as its example, and the last `<code><em>` as that
+/// example's answer. Part 2 usually reuses part 1's example without repeating it, so a part 2
+/// without an example of its own, or with the same one, adds its answer to `dayN`. A different
+/// one becomes `dayN_part2`.
+pub fn extract_examples(day: u8, html: &str) -> Vec<Example> {
+    let article_re = Regex::new(r#"(?s)<article class="day-desc">(.*?)</article>"#).unwrap();
+    let pre_re = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let answer_re =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+
+    let mut examples: Vec<Example> = vec![];
+    for (i, article) in article_re.captures_iter(html).take(2).enumerate() {
+        let part = i as u8 + 1;
+        let article = &article[1];
+
+        let input = pre_re
+            .captures(article)
+            .map(|caps| decode_html(&strip_tags(&caps[1])));
+        let answer = answer_re.captures_iter(article).last().map(|caps| {
+            let text = decode_html(&strip_tags(caps.get(1).or(caps.get(2)).unwrap().as_str()));
+            text.parse::<i64>().map_or(Answer::Str(text), Answer::Int)
+        });
+
+        match (examples.first_mut(), input) {
+            (Some(first), None) => first.answers[i] = answer,
+            (Some(first), Some(input)) if first.input == input => first.answers[i] = answer,
+            (_, None) => {}
+            (_, Some(input)) => {
+                let mut answers = [None, None];
+                answers[i] = answer;
+                examples.push(Example {
+                    name: match part {
+                        1 => format!("day{day}"),
+                        _ => format!("day{day}_part{part}"),
+                    },
+                    input,
+                    answers,
+                });
+            }
+        }
+    }
+
+    examples
+}
+
+fn strip_tags(html: &str) -> String {
+    Regex::new(r"<[^>]*>")
+        .unwrap()
+        .replace_all(html, "")
+        .into_owned()
+}
+
+fn decode_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Downloads a day's puzzle page, and saves its examples to `inputs/<year>/example` along with
+/// their answers. Example files and answers that already exist are left alone.
+pub fn fetch_examples(year: u16, day: u8) -> Result<Vec<Example>> {
+    let html = AocClient::shared().download_puzzle(year, day)?;
+    let examples = extract_examples(day, &html);
+    if examples.is_empty() {
+        bail!("Couldn't find any examples on the puzzle page for day {day}");
+    }
+
+    let answers_path = make_answers_path(year, false);
+    let mut answers = Answers::load(&answers_path)?;
+
+    for example in &examples {
+        let path = make_path(&example.name, year, false);
+        if path.exists() {
+            eprintln!("{} already exists, leaving it alone", path.display());
+        } else {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&path, &example.input)?;
+            eprintln!("Saved an example to {}", path.display());
+        }
+
+        for (part, answer) in (1..=2).zip(&example.answers) {
+            if let Some(answer) = answer {
+                if answers.get(&example.name, part).is_none() {
+                    answers.set(&example.name, part, answer.clone());
+                }
+            }
+        }
+    }
+    answers.save(&answers_path)?;
+
+    Ok(examples)
+}
@@ -0,0 +1,359 @@
+use std::iter;
+use std::time::Duration;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::Serialize;
+
+use crate::answers::Check;
+use crate::{Answer, DayReport, FailedDay, PartReport};
+
+/// A line of the summary table: day, input, part, answer, time and check.
+type Row = [String; 6];
+
+/// A table of every step of every day that ran, and of the days that failed, with the total
+/// runtime underneath:
+/// ```text
+/// Day | Input      | Part  | Answer | Time     | Check
+/// ----+------------+-------+--------+----------+------
+///   3 | day3       | parse |        | 36.76µs  |
+///   3 | day3       | 1     | 161    | 15.78µs  | PASS
+///   3 | day3_part2 | parse |        | 30.12µs  |
+///   3 | day3_part2 | 2     | 48     | 20.40µs  | PASS
+///   4 |            |       |        |          | ERROR: ...
+/// ...
+/// Total: 1.23ms for 2 days, 1 day failed
+/// ```
+pub fn summary_table(reports: &[DayReport], failed: &[FailedDay]) -> String {
+    let header = [
+        "Day".to_string(),
+        "Input".to_string(),
+        "Part".to_string(),
+        "Answer".to_string(),
+        "Time".to_string(),
+        "Check".to_string(),
+    ];
+    // each day's rows, kept in day order with the failed days slotted in
+    let mut sections: Vec<((u16, u8), Vec<Row>)> = vec![];
+    for failure in failed {
+        let mut row: Row = Default::default();
+        row[0] = failure.day.to_string();
+        row[5] = format!("ERROR: {}", failure.error);
+        sections.push(((failure.year, failure.day), vec![row]));
+    }
+    for report in reports {
+        let mut rows = vec![[
+            report.day.to_string(),
+            report.input.clone(),
+            "parse".to_string(),
+            String::new(),
+            format_duration(report.parse),
+            String::new(),
+        ]];
+        for part in &report.parts {
+            let check = match part.answer {
+                Answer::Unimplemented => String::new(),
+                _ => part.check.to_string(),
+            };
+            rows.push([
+                part.day.to_string(),
+                part.input.clone(),
+                part.label(),
+                part.answer.to_string(),
+                format_duration(part.duration),
+                check,
+            ]);
+        }
+        sections.push(((report.year, report.day), rows));
+    }
+    sections.sort_by_key(|&(day, _)| day);
+    let rows: Vec<_> = iter::once(header)
+        .chain(sections.into_iter().flat_map(|(_, rows)| rows))
+        .collect();
+
+    let widths: Vec<usize> = (0..6)
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap()
+        })
+        .collect();
+
+    let mut table = String::new();
+    for (i, row) in rows.iter().enumerate() {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(col, (cell, &width))| match col {
+                // days line up on the right, like numbers
+                0 if i > 0 => format!("{cell:>width$}"),
+                _ => format!("{cell:<width$}"),
+            })
+            .collect();
+        table.push_str(cells.join(" | ").trim_end());
+        table.push('\n');
+
+        if i == 0 {
+            let rule: Vec<_> = widths.iter().map(|&width| "-".repeat(width)).collect();
+            table.push_str(&rule.join("-+-"));
+            table.push('\n');
+        }
+    }
+
+    let total: Duration = reports.iter().map(DayReport::total).sum();
+    let mut days: Vec<_> = reports
+        .iter()
+        .map(|report| (report.year, report.day))
+        .collect();
+    days.dedup();
+    let mut summary = format!(
+        "Total: {} for {}",
+        format_duration(total),
+        plural_days(days.len())
+    );
+    if !failed.is_empty() {
+        summary.push_str(&format!(", {} failed", plural_days(failed.len())));
+    }
+    table.push_str(&summary);
+    table.push('\n');
+
+    table
+}
+
+/// One part's result, or a day that failed, as it appears in the JSON and CSV reports. Every field
+/// is always there, with `null` (an empty CSV field) where it doesn't apply.
+#[derive(Debug, Serialize)]
+struct PartRecord<'a> {
+    year: u16,
+    day: u8,
+    /// `null` for failed days.
+    input: Option<&'a str>,
+    /// `null` for failed days.
+    part: Option<u8>,
+    /// `null` unless the part was solved by a variant.
+    variant: Option<&'a str>,
+    /// `null` for unimplemented parts and failed days.
+    answer: Option<&'a Answer>,
+    status: &'static str,
+    /// `null` unless the answer was wrong.
+    expected: Option<&'a Answer>,
+    /// `null` unless the day failed.
+    error: Option<&'a str>,
+    time_ns: u64,
+}
+
+impl<'a> PartRecord<'a> {
+    fn new(part: &'a PartReport) -> Self {
+        PartRecord {
+            year: part.year,
+            day: part.day,
+            input: Some(&part.input),
+            part: Some(part.part),
+            variant: part.variant,
+            answer: match part.answer {
+                Answer::Unimplemented => None,
+                _ => Some(&part.answer),
+            },
+            status: status(part),
+            expected: match &part.check {
+                Check::Fail(expected) => Some(expected),
+                _ => None,
+            },
+            error: None,
+            time_ns: part.duration.as_nanos() as u64,
+        }
+    }
+
+    fn failed(failure: &'a FailedDay) -> Self {
+        PartRecord {
+            year: failure.year,
+            day: failure.day,
+            input: None,
+            part: None,
+            variant: None,
+            answer: None,
+            status: "error",
+            expected: None,
+            error: Some(&failure.error),
+            time_ns: 0,
+        }
+    }
+}
+
+/// `pass`, `fail`, `unknown` when there's no known answer to check against, or `unimplemented`.
+/// Days that failed to run are `error`.
+fn status(part: &PartReport) -> &'static str {
+    match (&part.answer, &part.check) {
+        (Answer::Unimplemented, _) => "unimplemented",
+        (_, Check::Pass) => "pass",
+        (_, Check::Fail(_)) => "fail",
+        (_, Check::Unknown) => "unknown",
+    }
+}
+
+/// The parts in day order, with the failed days slotted in.
+fn records<'a>(reports: &'a [DayReport], failed: &'a [FailedDay]) -> Vec<PartRecord<'a>> {
+    let mut records: Vec<_> = reports
+        .iter()
+        .flat_map(|report| &report.parts)
+        .map(PartRecord::new)
+        .chain(failed.iter().map(PartRecord::failed))
+        .collect();
+    records.sort_by_key(|record| (record.year, record.day));
+    records
+}
+
+/// An array with an object per part, and one per failed day:
+/// ```json
+/// [{ "year": 2024, "day": 3, "input": "day3_part2", "part": 2, "variant": null, "answer": 48,
+///    "status": "pass", "expected": null, "error": null, "time_ns": 1977234 }]
+/// ```
+pub fn to_json(reports: &[DayReport], failed: &[FailedDay]) -> Result<String> {
+    Ok(serde_json::to_string_pretty(&records(reports, failed))?)
+}
+
+/// A header, then a row per part or failed day with the same fields as the JSON report.
+pub fn to_csv(reports: &[DayReport], failed: &[FailedDay]) -> String {
+    let mut csv = "year,day,input,part,variant,answer,status,expected,error,time_ns\n".to_string();
+    for record in records(reports, failed) {
+        let answer = |answer: Option<&Answer>| answer.map(|answer| csv_field(&answer.to_string()));
+        let row = [
+            record.year.to_string(),
+            record.day.to_string(),
+            record.input.map(csv_field).unwrap_or_default(),
+            record.part.map(|part| part.to_string()).unwrap_or_default(),
+            record.variant.map(csv_field).unwrap_or_default(),
+            answer(record.answer).unwrap_or_default(),
+            record.status.to_string(),
+            answer(record.expected).unwrap_or_default(),
+            record.error.map(csv_field).unwrap_or_default(),
+            record.time_ns.to_string(),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/// Quotes a field when it holds anything CSV gives a meaning to.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// A JUnit XML report with a test suite per day and a test case per part. Wrong answers are
+/// failures, parts that are unimplemented or have no known answer are skipped, and a day that
+/// failed to run is a single test case with an error.
+pub fn to_junit(reports: &[DayReport], failed: &[FailedDay]) -> String {
+    let mut days: IndexMap<(u16, u8), Vec<&DayReport>> = IndexMap::new();
+    for report in reports {
+        days.entry((report.year, report.day))
+            .or_default()
+            .push(report);
+    }
+    for failure in failed {
+        days.entry((failure.year, failure.day)).or_default();
+    }
+    days.sort_keys();
+
+    let parts: Vec<_> = reports.iter().flat_map(|report| &report.parts).collect();
+    let total: Duration = reports.iter().map(DayReport::total).sum();
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <testsuites name=\"aoc\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" errors=\"{}\" time=\"{}\">\n",
+        parts.len() + failed.len(),
+        count(&parts, &["fail"]),
+        count(&parts, &["unknown", "unimplemented"]),
+        failed.len(),
+        total.as_secs_f64(),
+    );
+    for ((year, day), reports) in days {
+        if let Some(failure) = failed
+            .iter()
+            .find(|failure| (failure.year, failure.day) == (year, day))
+        {
+            xml.push_str(&format!(
+                "  <testsuite name=\"{year} day {day}\" tests=\"1\" failures=\"0\" skipped=\"0\" errors=\"1\" time=\"0\">\n"
+            ));
+            xml.push_str(&format!(
+                "    <testcase classname=\"{year}.day{day}\" name=\"day {day}\" time=\"0\">\n"
+            ));
+            xml.push_str(&format!(
+                "      <error message=\"{}\"/>\n",
+                xml_escape(&failure.error)
+            ));
+            xml.push_str("    </testcase>\n  </testsuite>\n");
+            continue;
+        }
+        let parts: Vec<_> = reports.iter().flat_map(|report| &report.parts).collect();
+        let total: Duration = reports.iter().map(|report| report.total()).sum();
+        xml.push_str(&format!(
+            "  <testsuite name=\"{year} day {day}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\" errors=\"0\" time=\"{}\">\n",
+            parts.len(),
+            count(&parts, &["fail"]),
+            count(&parts, &["unknown", "unimplemented"]),
+            total.as_secs_f64(),
+        ));
+        for part in parts {
+            xml.push_str(&format!(
+                "    <testcase classname=\"{year}.day{day}\" name=\"{} part {}\" time=\"{}\"",
+                xml_escape(&part.input),
+                xml_escape(&part.label()),
+                part.duration.as_secs_f64(),
+            ));
+            let body = match (status(part), &part.check) {
+                ("fail", Check::Fail(expected)) => format!(
+                    "<failure message=\"expected {}, got {}\"/>",
+                    xml_escape(&expected.to_string()),
+                    xml_escape(&part.answer.to_string())
+                ),
+                ("unknown", _) => {
+                    "<skipped message=\"no known answer to check against\"/>".to_string()
+                }
+                ("unimplemented", _) => "<skipped message=\"not implemented\"/>".to_string(),
+                _ => String::new(),
+            };
+            if body.is_empty() {
+                xml.push_str("/>\n");
+            } else {
+                xml.push_str(&format!(">\n      {body}\n    </testcase>\n"));
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+
+    xml
+}
+
+/// How many of the parts have one of the statuses.
+fn count(parts: &[&PartReport], statuses: &[&str]) -> usize {
+    parts
+        .iter()
+        .filter(|part| statuses.contains(&status(part)))
+        .count()
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn plural_days(n: usize) -> String {
+    match n {
+        1 => "1 day".to_string(),
+        n => format!("{n} days"),
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    format!("{duration:.2?}")
+}
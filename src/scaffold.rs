@@ -0,0 +1,239 @@
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::answers::Answers;
+use crate::puzzle::fetch_examples;
+use crate::{make_answers_path, make_path};
+
+const README: &str = include_str!("days/README.md");
+
+/// The day's source file, from the template in `src/days/README.md`.
+pub fn render_template(day: u8) -> Result<String> {
+    let (_, rest) = README
+        .split_once("```rust\n")
+        .ok_or_else(|| anyhow!("Couldn't find the template in src/days/README.md"))?;
+    let (template, _) = rest
+        .split_once("```")
+        .ok_or_else(|| anyhow!("The template in src/days/README.md isn't closed"))?;
+
+    Ok(template
+        .replace("DayX", &format!("Day{day}"))
+        .replace("const DAY: u8 = X;", &format!("const DAY: u8 = {day};")))
+}
+
+/// The `mod.rs` of a year without any days yet.
+pub fn render_year(year: u16) -> String {
+    format!(
+        "use crate::Day;
+
+/// Every solved day of {year}, in the order that `all` runs them.
+pub const DAYS: &[Day] = &[];
+"
+    )
+}
+
+/// Adds `pub mod dayN;` and the day's `DAYS` entry to the contents of `src/days/yYYYY/mod.rs`,
+/// keeping the modules in the order rustfmt wants, and `DAYS` in day order.
+pub fn register_day(mod_rs: &str, day: u8) -> Result<String> {
+    let entry_day = |line: &str| {
+        line.trim()
+            .strip_prefix("Day::new::<day")
+            .and_then(|rest| rest.split_once("::"))
+            .and_then(|(n, _)| n.parse::<u8>().ok())
+    };
+
+    register(
+        mod_rs,
+        &format!("day{day}"),
+        "DAYS",
+        &format!("    Day::new::<day{day}::Day{day}>(),"),
+        |line| entry_day(line).is_some_and(|n| n > day),
+        |line| entry_day(line).is_some(),
+    )
+    .map_err(|err| anyhow!("Day {day}: {err}"))
+}
+
+/// Adds `pub mod yYYYY;` and the year's `YEARS` entry to the contents of `src/days/mod.rs`.
+pub fn register_year(mod_rs: &str, year: u16) -> Result<String> {
+    let entry_year = |line: &str| {
+        line.trim()
+            .strip_prefix("Year::new(")
+            .and_then(|rest| rest.split_once(','))
+            .and_then(|(n, _)| n.parse::<u16>().ok())
+    };
+
+    register(
+        mod_rs,
+        &format!("y{year}"),
+        "YEARS",
+        &format!("    Year::new({year}, y{year}::DAYS),"),
+        |line| entry_year(line).is_some_and(|n| n > year),
+        |line| entry_year(line).is_some(),
+    )
+    .map_err(|err| anyhow!("Year {year}: {err}"))
+}
+
+/// Adds a `pub mod` line in name order, and an entry to the `list` const in the order given by
+/// `comes_after`, which tells whether an existing entry belongs after the new one.
+fn register(
+    mod_rs: &str,
+    module: &str,
+    list: &str,
+    entry: &str,
+    comes_after: impl Fn(&str) -> bool,
+    is_entry: impl Fn(&str) -> bool,
+) -> Result<String> {
+    let module_line = format!("pub mod {module};");
+    if mod_rs.lines().any(|line| line == module_line) {
+        bail!("already registered");
+    }
+
+    let list_start = format!("pub const {list}: ");
+    let mut lines: Vec<String> = vec![];
+    for line in mod_rs.lines() {
+        // rustfmt puts short lists on a single line, so spread them out to have one entry per line
+        match line
+            .strip_suffix("];")
+            .filter(|_| line.starts_with(&list_start))
+            .and_then(|line| line.split_once(" = &["))
+        {
+            Some((start, entries)) => {
+                lines.push(format!("{start} = &["));
+                lines.extend(
+                    split_entries(entries)
+                        .into_iter()
+                        .map(|entry| format!("    {entry},")),
+                );
+                lines.push("];".to_string());
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+
+    let module_name = |line: &str| {
+        line.strip_prefix("pub mod ")
+            .and_then(|rest| rest.strip_suffix(';'))
+            .map(String::from)
+    };
+    match lines
+        .iter()
+        .position(|line| module_name(line).is_some_and(|other| other.as_str() > module))
+        .or_else(|| {
+            lines
+                .iter()
+                .rposition(|line| module_name(line).is_some())
+                .map(|i| i + 1)
+        }) {
+        Some(idx) => lines.insert(idx, module_line),
+        None => {
+            // the first module goes in its own block after the imports
+            let idx = lines
+                .iter()
+                .rposition(|line| line.starts_with("use "))
+                .ok_or_else(|| anyhow!("couldn't find where the modules go"))?;
+            lines.splice(idx + 1..idx + 1, [String::new(), module_line]);
+        }
+    }
+
+    let entry_idx = lines
+        .iter()
+        .position(|line| comes_after(line))
+        .or_else(|| lines.iter().rposition(|line| is_entry(line)).map(|i| i + 1))
+        .or_else(|| {
+            lines
+                .iter()
+                .position(|line| line.starts_with(&list_start))
+                .map(|i| i + 1)
+        })
+        .ok_or_else(|| anyhow!("couldn't find {list}"))?;
+    lines.insert(entry_idx, entry.to_string());
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Splits a list's entries at the commas that aren't nested in brackets.
+fn split_entries(entries: &str) -> Vec<&str> {
+    let mut split = vec![];
+    let (mut depth, mut start) = (0, 0);
+    for (i, c) in entries.char_indices() {
+        match c {
+            '(' | '<' | '[' => depth += 1,
+            ')' | '>' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(entries[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(entries[start..].trim());
+    split.retain(|entry| !entry.is_empty());
+
+    split
+}
+
+fn make_days_dir() -> PathBuf {
+    let mut path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+
+    path.push("src");
+    path.push("days");
+
+    path
+}
+
+/// Creates `src/days/yYYYY/dayN.rs` (and the year's module when it's the year's first day),
+/// registers it, and sets up its example input and a stub for its answers. Refuses to touch a day
+/// that already has a source file.
+pub fn new_day(year: u16, day: u8) -> Result<()> {
+    let days_dir = make_days_dir();
+    let year_dir = days_dir.join(format!("y{year}"));
+    let source_path = year_dir.join(format!("day{day}.rs"));
+    if source_path.exists() {
+        bail!("{} already exists", source_path.display());
+    }
+
+    let mod_path = year_dir.join("mod.rs");
+    if !mod_path.exists() {
+        let years_path = days_dir.join("mod.rs");
+        let years_rs = register_year(&fs::read_to_string(&years_path)?, year)?;
+
+        fs::create_dir_all(&year_dir)?;
+        fs::write(&mod_path, render_year(year))?;
+        fs::write(&years_path, years_rs)?;
+        println!("Registered {year} in {}", years_path.display());
+    }
+    let mod_rs = register_day(&fs::read_to_string(&mod_path)?, day)?;
+
+    fs::write(&source_path, render_template(day)?)?;
+    println!("Created {}", source_path.display());
+    fs::write(&mod_path, mod_rs)?;
+    println!("Registered day {day} in {}", mod_path.display());
+
+    let name = format!("day{day}");
+    if let Err(err) = fetch_examples(year, day) {
+        eprintln!("Couldn't fetch the examples for day {day}: {err:#}");
+    }
+    let example_path = make_path(&name, year, false);
+    if !example_path.exists() {
+        if let Some(dir) = example_path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&example_path, "")?;
+        println!("Created an empty {}", example_path.display());
+    }
+
+    let answers_path = make_answers_path(year, false);
+    let mut answers = Answers::load(&answers_path)?;
+    if !answers.contains(&name) {
+        answers.stub(&name);
+        answers.save(&answers_path)?;
+        println!(
+            "Added a pending [{name}] to {}, fill in its answers or save them with --save",
+            answers_path.display()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use std::env;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::client::AocClient;
+use crate::config;
+use crate::error::Error;
+
+/// The session token to talk to the site with. `AOC_SESSION` comes first, then the `session_file`
+/// from `aoc.toml`, then the token saved by `login`.
+pub fn session_token() -> Option<String> {
+    env::var("AOC_SESSION")
+        .ok()
+        .or_else(|| read_token(&config().session_file()?))
+        .or_else(|| read_token(&user_session_file()?))
+}
+
+/// Where `login` saves the token: `$XDG_CONFIG_HOME/aoc/session`, or `~/.config/aoc/session`.
+pub fn user_session_file() -> Option<PathBuf> {
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(env::var_os("HOME")?).join(".config")))?;
+
+    Some(config_dir.join("aoc").join("session"))
+}
+
+/// An empty file counts as no token.
+pub fn read_token(path: &Path) -> Option<String> {
+    let token = fs::read_to_string(path).ok()?;
+    Some(token.trim().to_string()).filter(|token| !token.is_empty())
+}
+
+/// Saves the token where only the current user can read it.
+pub fn save_token(path: &Path, token: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Couldn't create {}", dir.display()))?;
+    }
+
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file = options
+        .open(path)
+        .with_context(|| format!("Couldn't write {}", path.display()))?;
+    // the mode only applies to new files
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    writeln!(file, "{token}")?;
+
+    Ok(())
+}
+
+/// Accepts the token as it's copied from the browser, with or without the `session=` in front.
+pub fn clean_token(token: &str) -> Result<String> {
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    if token.is_empty() {
+        bail!("The session token is empty");
+    }
+    if !token.chars().all(|c| c.is_ascii_alphanumeric()) {
+        bail!("The session token should only hold letters and digits");
+    }
+
+    Ok(token.to_string())
+}
+
+/// Checks the token with the site, and saves it for later runs. Without a token, it's read from
+/// stdin, which keeps it out of the shell's history.
+pub fn login(token: Option<String>) -> Result<(), Error> {
+    let token = match token {
+        Some(token) => token,
+        None => {
+            eprint!("Paste the `session` cookie from adventofcode.com: ");
+            let mut token = String::new();
+            io::stdin()
+                .lock()
+                .read_line(&mut token)
+                .context("Couldn't read the session token")
+                .map_err(Error::Other)?;
+            token
+        }
+    };
+    let token = clean_token(&token).map_err(Error::Other)?;
+
+    let user = AocClient::from_env()
+        .with_session(token.clone())
+        .whoami(config().year)
+        .map_err(Error::network)?;
+
+    let path = user_session_file()
+        .context("Couldn't tell where to save the token, set HOME or XDG_CONFIG_HOME")
+        .map_err(Error::Other)?;
+    save_token(&path, &token).map_err(Error::Other)?;
+    println!("Logged in as {user}, saved the token to {}", path.display());
+
+    Ok(())
+}
+
+/// Tells who the session token belongs to, which also checks that the site still accepts it.
+pub fn whoami() -> Result<(), Error> {
+    let user = AocClient::shared()
+        .whoami(config().year)
+        .map_err(Error::network)?;
+    println!("Logged in as {user}");
+
+    Ok(())
+}
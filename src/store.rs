@@ -0,0 +1,43 @@
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+/// How a file the tool keeps state in is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    Toml,
+    Json,
+}
+
+/// Reads a file the tool keeps state in. A missing file is treated the same as an empty one.
+pub fn load_or_default<T: DeserializeOwned + Default>(
+    path: &Path,
+    format: FileFormat,
+) -> Result<T> {
+    if !path.exists() {
+        return Ok(T::default());
+    }
+
+    let contents = fs::read_to_string(path)?;
+    let value = match format {
+        FileFormat::Toml => toml::from_str(&contents).map_err(anyhow::Error::from),
+        FileFormat::Json => serde_json::from_str(&contents).map_err(anyhow::Error::from),
+    };
+    value.with_context(|| format!("{} is malformed", path.display()))
+}
+
+/// Writes a file the tool keeps state in, creating its folder when it's the first one there.
+pub fn save_creating_dirs<T: Serialize>(value: &T, path: &Path, format: FileFormat) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let contents = match format {
+        FileFormat::Toml => toml::to_string(value)?,
+        FileFormat::Json => serde_json::to_string_pretty(value)?,
+    };
+    fs::write(path, contents)?;
+    Ok(())
+}
@@ -0,0 +1,182 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Result};
+use indexmap::IndexMap;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::answers::Answers;
+use crate::client::AocClient;
+use crate::error::Error;
+use crate::store::{load_or_default, save_creating_dirs, FileFormat};
+use crate::{make_answers_path, make_state_dir, Answer, PartReport};
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Submitted too soon after the previous (wrong) answer; try again after the wait.
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Reads the outcome from the page the site returns after a submission.
+    pub fn parse(html: &str) -> Result<Self> {
+        if html.contains("That's the right answer") {
+            Ok(Outcome::Correct)
+        } else if html.contains("your answer is too high") {
+            Ok(Outcome::TooHigh)
+        } else if html.contains("your answer is too low") {
+            Ok(Outcome::TooLow)
+        } else if html.contains("That's not the right answer") {
+            Ok(Outcome::Wrong)
+        } else if html.contains("You gave an answer too recently") {
+            let re = Regex::new(r"You have (?:(\d+)m ?)?(?:(\d+)s )?left to wait").unwrap();
+            let wait = re
+                .captures(html)
+                .map(|caps| {
+                    let minutes: u64 = caps.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+                    let seconds: u64 = caps.get(2).map_or(0, |s| s.as_str().parse().unwrap());
+                    Duration::from_secs(minutes * 60 + seconds)
+                })
+                .unwrap_or_default();
+            Ok(Outcome::RateLimited(wait))
+        } else if html.contains("You don't seem to be solving the right level") {
+            Ok(Outcome::AlreadySolved)
+        } else {
+            Err(anyhow!(
+                "Couldn't make sense of the submission response:\n{html}"
+            ))
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct!"),
+            Outcome::TooHigh => write!(f, "wrong, too high"),
+            Outcome::TooLow => write!(f, "wrong, too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+            Outcome::RateLimited(wait) => write!(f, "rate limited, wait {wait:?} and try again"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// What previous submissions taught us about one part's answer.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct History {
+    #[serde(default)]
+    pub wrong: Vec<Answer>,
+    /// The largest answer known to be too low.
+    pub too_low: Option<i64>,
+    /// The smallest answer known to be too high.
+    pub too_high: Option<i64>,
+}
+
+/// Remembers wrong answers per part, so that they (and anything outside the bounds learnt from
+/// "too high" and "too low") are refused locally instead of costing a lockout.
+///
+/// Keyed by `<year>/day<day>/part<part>`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Guard(IndexMap<String, History>);
+
+impl Guard {
+    pub fn load(path: &Path) -> Result<Self> {
+        load_or_default(path, FileFormat::Json)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        save_creating_dirs(self, path, FileFormat::Json)
+    }
+
+    /// Errors when the answer is already known to be wrong.
+    pub fn check(&self, key: &str, answer: &Answer) -> Result<()> {
+        let Some(history) = self.0.get(key) else {
+            return Ok(());
+        };
+
+        if history.wrong.contains(answer) {
+            bail!("{answer} was already submitted, and it was wrong");
+        }
+        if let Answer::Int(n) = answer {
+            if let Some(low) = history.too_low.filter(|&low| *n <= low) {
+                bail!("{answer} is too low, {low} was already too low");
+            }
+            if let Some(high) = history.too_high.filter(|&high| *n >= high) {
+                bail!("{answer} is too high, {high} was already too high");
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn record(&mut self, key: &str, answer: &Answer, outcome: Outcome) {
+        let history = self.0.entry(key.to_string()).or_default();
+        match (outcome, answer) {
+            (Outcome::TooHigh, Answer::Int(n)) => {
+                history.too_high = Some(history.too_high.map_or(*n, |high| high.min(*n)));
+            }
+            (Outcome::TooLow, Answer::Int(n)) => {
+                history.too_low = Some(history.too_low.map_or(*n, |low| low.max(*n)));
+            }
+            _ => {}
+        }
+        if matches!(outcome, Outcome::TooHigh | Outcome::TooLow | Outcome::Wrong) {
+            history.wrong.push(answer.clone());
+        }
+    }
+}
+
+fn make_guard_path() -> PathBuf {
+    let mut path = make_state_dir();
+
+    path.push("submissions.json");
+
+    path
+}
+
+/// Submits a part's answer, and stores it in the real answers file when it's correct.
+/// Answers that earlier submissions rule out are refused without contacting the site.
+pub fn submit(report: &PartReport) -> Result<Outcome, Error> {
+    if report.answer == Answer::Unimplemented {
+        return Err(Error::Other(anyhow!(
+            "Day {} part {} isn't implemented yet",
+            report.day,
+            report.part
+        )));
+    }
+
+    let guard_path = make_guard_path();
+    let mut guard = Guard::load(&guard_path).map_err(Error::Other)?;
+    let key = format!("{}/day{}/part{}", report.year, report.day, report.part);
+    guard.check(&key, &report.answer).map_err(Error::Other)?;
+
+    let outcome = AocClient::shared()
+        .submit_answer(
+            report.year,
+            report.day,
+            report.part,
+            &report.answer.to_string(),
+        )
+        .map_err(Error::network)?;
+
+    guard.record(&key, &report.answer, outcome);
+    guard.save(&guard_path).map_err(Error::Other)?;
+
+    if outcome == Outcome::Correct {
+        let path = make_answers_path(report.year, true);
+        let mut answers = Answers::load(&path).map_err(Error::Input)?;
+        answers.set(&report.input, report.part, report.answer.clone());
+        answers.save(&path).map_err(Error::Input)?;
+    }
+
+    Ok(outcome)
+}
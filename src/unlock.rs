@@ -0,0 +1,82 @@
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::error::Error;
+
+/// Puzzles unlock at midnight US Eastern time, which is always 5 hours behind UTC in December.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// How long to keep waiting past the unlock, in case the local clock runs a little ahead.
+const MARGIN: Duration = Duration::from_secs(1);
+
+/// When a day's puzzle unlocks.
+pub fn unlock_time(year: u16, day: u8) -> SystemTime {
+    let days = days_since_epoch(year.into(), 12, day.into());
+    UNIX_EPOCH + Duration::from_secs(days * 24 * 60 * 60 + UNLOCK_HOUR_UTC * 60 * 60)
+}
+
+/// How long is left until the puzzle unlocks, or `None` once it has.
+pub fn time_until_unlock(year: u16, day: u8, now: SystemTime) -> Option<Duration> {
+    unlock_time(year, day)
+        .duration_since(now)
+        .ok()
+        .filter(|remaining| !remaining.is_zero())
+}
+
+/// Fails with [`Error::Locked`] before the puzzle unlocks, so that the site isn't asked for it.
+pub fn ensure_unlocked(year: u16, day: u8) -> Result<(), Error> {
+    match time_until_unlock(year, day, SystemTime::now()) {
+        Some(remaining) => Err(Error::Locked {
+            year,
+            day,
+            remaining,
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Sleeps until the puzzle unlocks, counting down on stderr.
+pub fn wait_for_unlock(year: u16, day: u8) {
+    let mut waited = false;
+    while let Some(remaining) = time_until_unlock(year, day, SystemTime::now()) {
+        eprint!(
+            "\rDay {day} of {year} unlocks in {}, waiting...",
+            format_countdown(remaining)
+        );
+        thread::sleep(remaining.min(Duration::from_secs(1)));
+        waited = true;
+    }
+
+    if waited {
+        eprintln!();
+        thread::sleep(MARGIN);
+    }
+}
+
+/// Like `2d 03h 04m 05s`, leaving the days out when there are none. Rounds up, so that the
+/// countdown only reaches zero at the unlock.
+pub fn format_countdown(remaining: Duration) -> String {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    let (days, hours, minutes, seconds) = (
+        secs / (24 * 60 * 60),
+        secs / (60 * 60) % 24,
+        secs / 60 % 60,
+        secs % 60,
+    );
+
+    match days {
+        0 => format!("{hours:02}h {minutes:02}m {seconds:02}s"),
+        _ => format!("{days}d {hours:02}h {minutes:02}m {seconds:02}s"),
+    }
+}
+
+/// The number of days from 1970-01-01 to a date, using Howard Hinnant's `days_from_civil`.
+fn days_since_epoch(year: i64, month: i64, day: i64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    (era * 146_097 + day_of_era - 719_468) as u64
+}
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+use std::env;
+use std::fs;
+use std::path::{self, Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::{anyhow, bail, Context, Result};
+use indexmap::IndexMap;
+use notify_debouncer_mini::{new_debouncer, notify::RecursiveMode};
+
+use crate::{input_files, make_path, Opt};
+
+/// Set on the runs that `--watch` starts, which run the day once however the flag was spelled.
+pub const CHILD_ENV: &str = "AOC_WATCH_CHILD";
+
+/// How long to wait for a burst of saves to settle before rebuilding.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// The answers printed by a run, keyed by day, input and part.
+pub type RunAnswers = IndexMap<(u8, String, u8), String>;
+
+/// Reads the answers back out of a run's `Day N Part P: <answer> [<check>]` lines, each belonging
+/// to the input named by the `Day N Input: <name>` line before it.
+pub fn parse_answers(output: &str) -> RunAnswers {
+    let mut input = String::new();
+    output
+        .lines()
+        .filter_map(|line| {
+            let rest = line.strip_prefix("Day ")?;
+            if let Some((_, name)) = rest.split_once(" Input: ") {
+                input = name.to_string();
+                return None;
+            }
+            let (day, rest) = rest.split_once(" Part ")?;
+            let (part, answer) = rest.split_once(": ")?;
+            let answer = match answer.strip_suffix(']') {
+                Some(answer) => answer.rsplit_once(" [")?.0,
+                None => answer,
+            };
+            let key = (day.parse().ok()?, input.clone(), part.parse().ok()?);
+            Some((key, answer.to_string()))
+        })
+        .collect()
+}
+
+/// One line per part whose answer differs from the previous run's.
+pub fn diff_answers(previous: &RunAnswers, current: &RunAnswers) -> Vec<String> {
+    current
+        .iter()
+        .filter_map(|(key, answer)| {
+            let (day, input, part) = key;
+            let step = format!("Day {day} Part {part} ({input})");
+            match previous.get(key) {
+                Some(old) if old == answer => None,
+                Some(old) => Some(format!("{step}: {old} -> {answer}")),
+                None => Some(format!("{step}: new answer {answer}")),
+            }
+        })
+        .collect()
+}
+
+/// The files a day depends on: its source, the shared utilities, and its inputs.
+fn watched_files(year: u16, day: u8, opt: &Opt) -> Vec<PathBuf> {
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+    let mut files = vec![
+        src.join("days")
+            .join(format!("y{year}"))
+            .join(format!("day{day}.rs")),
+        src.join("utils.rs"),
+    ];
+
+    match &opt.input {
+        // the watcher reports absolute paths
+        Some(path) => files.push(path::absolute(path).unwrap_or_else(|_| path.clone())),
+        None => {
+            let inputs = input_files(year, day, opt);
+            if inputs.is_empty() {
+                // so that the run picks up the day's first example once it's written
+                files.push(make_path(&format!("day{day}"), year, false));
+            }
+            files.extend(
+                inputs
+                    .iter()
+                    .map(|input| make_path(&input.name, year, opt.real)),
+            );
+        }
+    }
+
+    files
+}
+
+/// Builds the runner the same way the current one was built.
+fn rebuild() -> Result<bool> {
+    let mut cargo = Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()));
+    cargo
+        .arg("build")
+        .arg("--quiet")
+        .current_dir(env!("CARGO_MANIFEST_DIR"));
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    if cfg!(feature = "alloc-stats") {
+        cargo.args(["--features", "alloc-stats"]);
+    }
+
+    Ok(cargo.status().context("Couldn't run cargo")?.success())
+}
+
+/// When each file was last modified, to tell edits apart from the runner merely reading them.
+fn modified(files: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    files
+        .iter()
+        .map(|file| fs::metadata(file).and_then(|meta| meta.modified()).ok())
+        .collect()
+}
+
+/// Whether this run was started by `--watch`, and so should ignore the flag.
+pub fn is_child() -> bool {
+    env::var_os(CHILD_ENV).is_some()
+}
+
+/// Runs the freshly built runner once with the same arguments, and returns what it printed.
+fn rerun(exe: &Path) -> Result<String> {
+    let output = Command::new(exe)
+        .args(env::args_os().skip(1))
+        .env(CHILD_ENV, "1")
+        .output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    print!("{stdout}");
+    eprint!("{}", String::from_utf8_lossy(&output.stderr));
+    if !output.status.success() {
+        println!("=== The run failed ({})", output.status);
+    }
+
+    Ok(stdout)
+}
+
+/// Rebuilds and reruns a day whenever its source, the utilities or its input change, and reports
+/// how the answers changed since the previous run.
+pub fn watch(year: u16, day: u8, opt: &Opt) -> Result<()> {
+    if opt.input.as_deref() == Some(Path::new("-")) {
+        bail!("Can't watch stdin, pass the input's path instead");
+    }
+    // resolved up front, since once cargo relinks the runner this process's own file is gone
+    let exe = env::current_exe()?;
+    let files = watched_files(year, day, opt);
+    let dirs: HashSet<_> = files.iter().filter_map(|file| file.parent()).collect();
+
+    let (tx, rx) = mpsc::channel();
+    let mut debouncer = new_debouncer(DEBOUNCE, tx)?;
+    // editors often save by replacing the file, which a watch on the file itself wouldn't survive
+    for dir in dirs {
+        fs::create_dir_all(dir)?;
+        debouncer
+            .watcher()
+            .watch(dir, RecursiveMode::NonRecursive)
+            .with_context(|| format!("Couldn't watch {}", dir.display()))?;
+    }
+
+    println!("=== Watching:");
+    for file in &files {
+        println!("===   {}", file.display());
+    }
+
+    let mut previous: Option<RunAnswers> = None;
+    let mut last_modified = modified(&files);
+    loop {
+        if rebuild()? {
+            let answers = parse_answers(&rerun(&exe)?);
+            if let Some(previous) = &previous {
+                let diff = diff_answers(previous, &answers);
+                if diff.is_empty() {
+                    println!("=== No answers changed");
+                }
+                for line in diff {
+                    println!("=== {line}");
+                }
+            }
+            if !answers.is_empty() {
+                previous = Some(answers);
+            }
+        } else {
+            println!("=== The build failed");
+        }
+        println!("=== Waiting for changes...");
+
+        loop {
+            let events = rx
+                .recv()
+                .map_err(|_| anyhow!("The file watcher stopped"))??;
+            if events.iter().any(|event| files.contains(&event.path))
+                && modified(&files) != last_modified
+            {
+                last_modified = modified(&files);
+                break;
+            }
+        }
+    }
+}
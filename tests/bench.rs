@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use adventofcode_2024::{
+    answers::Check,
+    bench::{write_json, Baseline, Stats, Verdict},
+    Answer, DayReport,
+};
+
+mod common;
+use common::{benched, day, part, stats};
+
+fn day3(input: &str, parse_micros: u64, part_micros: u64) -> DayReport {
+    let part = part(input, 1, Answer::Int(161), Check::Pass, part_micros);
+    benched(day(parse_micros, vec![part]))
+}
+
+fn micros(samples: &[u64]) -> Vec<Duration> {
+    samples.iter().map(|&s| Duration::from_micros(s)).collect()
+}
+
+#[test]
+fn summarizes_varied_samples() {
+    let odd = Stats::from_samples(micros(&[4, 100, 1, 3, 2]));
+    assert_eq!(odd.iterations, 5);
+    assert_eq!(odd.min, Duration::from_micros(1));
+    assert_eq!(odd.median, Duration::from_micros(3));
+    assert_eq!(odd.mean, Duration::from_micros(22));
+    assert_eq!(odd.p95, Duration::from_micros(100));
+    // sqrt(7610 / 4), the sample stddev, rather than sqrt(7610 / 5)
+    assert_eq!(odd.stddev.as_nanos(), 43_618);
+
+    let even = Stats::from_samples(micros(&(1..=20).rev().collect::<Vec<_>>()));
+    assert_eq!(even.median, Duration::from_nanos(10_500));
+    assert_eq!(even.mean, Duration::from_nanos(10_500));
+    // the 19th of 20, by nearest rank
+    assert_eq!(even.p95, Duration::from_micros(19));
+    // sqrt(35), as the variance of 1..=n is n(n + 1) / 12
+    assert_eq!(even.stddev.as_nanos(), 5_916);
+}
+
+#[test]
+fn compares_each_example_against_its_own_baseline() {
+    let mut baseline = Baseline::default();
+    baseline.record(
+        "example",
+        &[day3("day3", 10, 100), day3("day3_small", 1, 10)],
+    );
+
+    let small = day3("day3_small", 1, 10);
+    let verdict = baseline.compare("example", &small.parts[0], &stats(10), 5.0);
+    assert_eq!(verdict, Verdict::Unchanged(0.0));
+    let verdict = baseline.compare("real", &small.parts[0], &stats(10), 5.0);
+    assert_eq!(verdict, Verdict::Missing);
+}
+
+#[test]
+fn only_counts_changes_past_the_threshold() {
+    let mut baseline = Baseline::default();
+    baseline.record("real", &[day3("day3", 10, 100)]);
+    let part = &day3("day3", 10, 100).parts[0];
+    let verdict = |micros| baseline.compare("real", part, &stats(micros), 5.0);
+
+    assert!(matches!(verdict(104), Verdict::Unchanged(_)));
+    assert!(matches!(verdict(106), Verdict::Regressed(_)));
+    assert_eq!(verdict(106).to_string(), "regressed by 6.0%");
+    assert!(matches!(verdict(96), Verdict::Unchanged(_)));
+    assert!(matches!(verdict(94), Verdict::Improved(_)));
+    assert_eq!(verdict(94).to_string(), "improved by 6.0%");
+}
+
+#[test]
+fn writes_parsing_and_the_input_to_the_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bench.json");
+    write_json(&path, &[day3("day3_small", 2, 20)]).unwrap();
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+    assert_eq!(json[0]["part"], "parse");
+    assert_eq!(json[0]["input"], "day3_small");
+    assert_eq!(json[0]["median_ns"], 2_000);
+    assert_eq!(json[1]["part"], 1);
+    assert_eq!(json[1]["input"], "day3_small");
+}
+
+#[test]
+fn treats_a_zero_median_as_no_baseline() {
+    let mut baseline = Baseline::default();
+    baseline.record("example", &[day3("day3", 0, 0)]);
+
+    let report = day3("day3", 10, 100);
+    let verdict = baseline.compare("example", &report.parts[0], &stats(100), 5.0);
+    assert_eq!(verdict, Verdict::Missing);
+}
+
+#[test]
+fn refuses_a_missing_baseline() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("no-baseline.json");
+    let err = Baseline::load(&path).unwrap_err();
+    assert!(err.to_string().contains(path.to_str().unwrap()), "{err}");
+    assert!(Baseline::load_or_default(&path).is_ok());
+}
@@ -0,0 +1,285 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use adventofcode_2024::{
+    client::{check_input, parse_user, AocClient, DownloadError},
+    error::Error,
+    mock::{MockServer, LOGGED_OUT_INPUT, LOGGED_OUT_PAGE, NOT_UNLOCKED_INPUT},
+    submit::Outcome,
+};
+
+#[test]
+fn downloads_input_with_session_cookie() {
+    let mock = MockServer::start().unwrap();
+    mock.require_session("abc123");
+    mock.input(2024, 1, "3   4\n4   3\n");
+
+    let client = AocClient::new(&mock.url(), Some("abc123".to_string()));
+    assert_eq!(client.download_input(2024, 1).unwrap(), "3   4\n4   3\n");
+
+    let requests = mock.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].path, "/2024/day/1/input");
+    assert_eq!(requests[0].header("cookie"), Some("session=abc123"));
+}
+
+#[test]
+fn refuses_to_download_without_session() {
+    let mock = MockServer::start().unwrap();
+    mock.input(2024, 1, "3   4\n");
+
+    let client = AocClient::new(&mock.url(), None);
+    let err = Error::network(client.download_input(2024, 1).unwrap_err());
+    assert!(matches!(err, Error::NoSession));
+    assert_eq!(err.exit_code(), 3);
+    assert!(mock.requests().is_empty());
+}
+
+#[test]
+fn submits_answer_as_form() {
+    let mock = MockServer::start().unwrap();
+    mock.require_session("abc123");
+    mock.answer(
+        2024,
+        1,
+        "<article><p>That's the right answer!</p></article>",
+    );
+
+    let client = AocClient::new(&mock.url(), Some("abc123".to_string()));
+    let outcome = client.submit_answer(2024, 1, 2, "31").unwrap();
+    assert_eq!(outcome, Outcome::Correct);
+
+    let requests = mock.requests();
+    assert_eq!(requests[0].method, "POST");
+    assert_eq!(requests[0].path, "/2024/day/1/answer");
+    assert_eq!(requests[0].body, "level=2&answer=31");
+}
+
+#[test]
+fn submits_an_answer_only_once() {
+    let mock = MockServer::start().unwrap();
+    mock.respond("POST", "/2024/day/1/answer", 502, "");
+
+    let client =
+        AocClient::new(&mock.url(), Some("abc123".to_string())).with_retry_delay(Duration::ZERO);
+    let err = client.submit_answer(2024, 1, 1, "11").unwrap_err();
+
+    assert_eq!(
+        err.downcast_ref::<DownloadError>(),
+        Some(&DownloadError::Status(502))
+    );
+    assert_eq!(mock.requests().len(), 1);
+}
+
+#[test]
+fn throttles_requests() {
+    let mock = MockServer::start().unwrap();
+    mock.puzzle(2024, 1, "<article></article>");
+
+    let client = AocClient::new(&mock.url(), None).with_min_interval(Duration::from_millis(200));
+    client.download_puzzle(2024, 1).unwrap();
+    client.download_puzzle(2024, 1).unwrap();
+
+    assert_spaced(&mock, &[200]);
+}
+
+/// Checks that the mock received each request at least `min` after the one before, give or take
+/// a little for when each end got scheduled.
+fn assert_spaced(mock: &MockServer, min: &[u64]) {
+    let requests = mock.requests();
+    assert_eq!(requests.len(), min.len() + 1);
+    for (pair, &min) in requests.windows(2).zip(min) {
+        let gap = pair[1].received - pair[0].received;
+        assert!(
+            gap >= Duration::from_millis(min * 9 / 10),
+            "{gap:?} < {min}ms"
+        );
+    }
+}
+
+/// Downloads day 1's input, and returns why it was turned down.
+fn refused_download(mock: &MockServer, session: &str) -> DownloadError {
+    let client =
+        AocClient::new(&mock.url(), Some(session.to_string())).with_retry_delay(Duration::ZERO);
+    let err = client.download_input(2024, 1).unwrap_err();
+    err.downcast().unwrap()
+}
+
+#[test]
+fn turns_down_refusals_from_the_site() {
+    let mock = MockServer::start().unwrap();
+    mock.require_session("abc123");
+    assert_eq!(refused_download(&mock, "expired"), DownloadError::LoggedOut);
+    assert_eq!(
+        refused_download(&mock, "abc123"),
+        DownloadError::NotUnlocked
+    );
+
+    let path = "/2024/day/1/input";
+    mock.respond("GET", path, 429, "slow down");
+    assert_eq!(
+        refused_download(&mock, "abc123"),
+        DownloadError::RateLimited
+    );
+    mock.respond("GET", path, 503, "");
+    assert_eq!(
+        refused_download(&mock, "abc123"),
+        DownloadError::Status(503)
+    );
+    mock.respond("GET", path, 200, "<!DOCTYPE html>\n<html></html>");
+    assert_eq!(refused_download(&mock, "abc123"), DownloadError::NotAnInput);
+}
+
+#[test]
+fn tells_inputs_from_refusals() {
+    assert_eq!(check_input(200, "3   4\n4   3\n"), Ok(()));
+    assert_eq!(
+        check_input(200, LOGGED_OUT_INPUT),
+        Err(DownloadError::LoggedOut)
+    );
+    assert_eq!(
+        check_input(404, NOT_UNLOCKED_INPUT),
+        Err(DownloadError::NotUnlocked)
+    );
+    assert_eq!(
+        check_input(404, "404 Not Found"),
+        Err(DownloadError::NotFound)
+    );
+    assert_eq!(check_input(200, ""), Err(DownloadError::NotAnInput));
+    assert_eq!(
+        check_input(200, "<!DOCTYPE html>\n<html></html>"),
+        Err(DownloadError::NotAnInput)
+    );
+    assert_eq!(
+        check_input(200, "<HTML><body></body></HTML>"),
+        Err(DownloadError::NotAnInput)
+    );
+    // inputs about brackets start with `<` as well
+    assert_eq!(
+        check_input(200, "<{([{{}}[<[[[<>{}]]]>[]]\n[({(<(())[]>[[{[]{<()<>>\n"),
+        Ok(())
+    );
+}
+
+#[test]
+fn sends_the_user_agent() {
+    let mock = MockServer::start().unwrap();
+    mock.puzzle(2024, 1, "<article></article>");
+
+    let client = AocClient::new(&mock.url(), None).with_user_agent("aoc-test (me@example.com)");
+    client.download_puzzle(2024, 1).unwrap();
+
+    let requests = mock.requests();
+    assert_eq!(
+        requests[0].header("user-agent"),
+        Some("aoc-test (me@example.com)")
+    );
+}
+
+#[test]
+fn retries_server_errors_with_backoff() {
+    let mock = MockServer::start().unwrap();
+    mock.respond("GET", "/2024/day/1", 500, "");
+
+    let client = AocClient::new(&mock.url(), None).with_retry_delay(Duration::from_millis(100));
+    let err = client.download_puzzle(2024, 1).unwrap_err();
+
+    assert_eq!(
+        err.downcast_ref::<DownloadError>(),
+        Some(&DownloadError::Status(500))
+    );
+    // 100ms, then 200ms, then 400ms
+    assert_spaced(&mock, &[100, 200, 400]);
+}
+
+#[test]
+fn reports_a_missing_puzzle_page() {
+    let mock = MockServer::start().unwrap();
+
+    let client = AocClient::new(&mock.url(), None);
+    let err = client.download_puzzle(2024, 1).unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<DownloadError>(),
+        Some(&DownloadError::NotFound)
+    );
+}
+
+#[test]
+fn keeps_the_interval_across_runs() {
+    let mock = MockServer::start().unwrap();
+    mock.puzzle(2024, 1, "<article></article>");
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("last-request");
+    let client = AocClient::new(&mock.url(), None)
+        .with_min_interval(Duration::from_millis(200))
+        .with_last_request_file(path.clone());
+    // as if another run had just sent a request, with a clock that's a bit ahead
+    let sent = SystemTime::now() + Duration::from_millis(300);
+    let millis = sent.duration_since(UNIX_EPOCH).unwrap().as_millis();
+    std::fs::write(&path, millis.to_string()).unwrap();
+
+    client.download_puzzle(2024, 1).unwrap();
+    let saved: u128 = std::fs::read_to_string(&path).unwrap().parse().unwrap();
+
+    assert!(SystemTime::now() >= sent + Duration::from_millis(200));
+    assert!(saved >= millis + 200);
+}
+
+#[test]
+fn refuses_requests_before_the_unlock() {
+    let mock = MockServer::start().unwrap();
+
+    let client = AocClient::new(&mock.url(), Some("abc123".to_string()));
+    let err = Error::network(client.download_input(2999, 1).unwrap_err());
+    assert!(matches!(
+        err,
+        Error::Locked {
+            year: 2999,
+            day: 1,
+            ..
+        }
+    ));
+    assert_eq!(err.exit_code(), 9);
+    assert!(err.to_string().contains("(at 2999-12-01 05:00 UTC)"));
+    assert!(client.download_puzzle(2999, 1).is_err());
+    assert!(mock.requests().is_empty());
+}
+
+#[test]
+fn tells_who_is_logged_in() {
+    let mock = MockServer::start().unwrap();
+    mock.require_session("abc123");
+    mock.user(2024, "someone");
+
+    let client = AocClient::new(&mock.url(), Some("abc123".to_string()));
+    assert_eq!(client.whoami(2024).unwrap(), "someone");
+    assert_eq!(mock.requests()[0].path, "/2024/settings");
+
+    let expired = AocClient::new(&mock.url(), Some("expired".to_string()));
+    let err = Error::network(expired.whoami(2024).unwrap_err());
+    assert!(matches!(err, Error::InvalidSession));
+    assert_eq!(err.exit_code(), 3);
+    assert!(err.to_string().contains("login"));
+}
+
+#[test]
+fn reports_an_expired_token_on_download() {
+    let mock = MockServer::start().unwrap();
+    mock.require_session("abc123");
+    mock.input(2024, 1, "3   4\n");
+
+    let client = AocClient::new(&mock.url(), Some("expired".to_string()));
+    let err = Error::network(client.download_input(2024, 1).unwrap_err());
+    assert!(matches!(err, Error::InvalidSession));
+}
+
+#[test]
+fn reads_the_user_from_a_page() {
+    assert_eq!(
+        parse_user(
+            r#"<div class="user">(anonymous user #1234) <span class="star-count">50*</span></div>"#
+        )
+        .as_deref(),
+        Some("(anonymous user #1234)")
+    );
+    assert_eq!(parse_user(LOGGED_OUT_PAGE), None);
+}
@@ -0,0 +1,54 @@
+//! Reports to feed the report and benchmark code, without running any day.
+
+// each test file only uses some of these
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use adventofcode_2024::{answers::Check, bench::Stats, Answer, DayReport, PartReport};
+
+/// A part's report, run once against `input`, a file name like `day3` or `day3_small` that the
+/// day is read from.
+pub fn part(input: &str, part: u8, answer: Answer, check: Check, micros: u64) -> PartReport {
+    let day = input[3..].split('_').next().unwrap().parse().unwrap();
+    PartReport {
+        year: 2024,
+        day,
+        part,
+        variant: None,
+        input: input.to_string(),
+        answer,
+        check,
+        duration: Duration::from_micros(micros),
+        stats: None,
+        alloc: None,
+        verdict: None,
+    }
+}
+
+/// The report of the day and input `parts` ran against.
+pub fn day(parse_micros: u64, parts: Vec<PartReport>) -> DayReport {
+    DayReport {
+        year: 2024,
+        day: parts[0].day,
+        input: parts[0].input.clone(),
+        parse: Duration::from_micros(parse_micros),
+        parse_stats: None,
+        parse_alloc: None,
+        parts,
+    }
+}
+
+/// Stats of samples that all took `micros`.
+pub fn stats(micros: u64) -> Stats {
+    Stats::from_samples(vec![Duration::from_micros(micros); 10])
+}
+
+/// `report` as if it was benchmarked, with each step taking as long every time.
+pub fn benched(mut report: DayReport) -> DayReport {
+    report.parse_stats = Some(stats(report.parse.as_micros() as u64));
+    for part in &mut report.parts {
+        part.stats = Some(stats(part.duration.as_micros() as u64));
+    }
+    report
+}
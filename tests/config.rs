@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use adventofcode_2024::{config::Config, Cli, Opt};
+use clap::error::ErrorKind;
+use clap::Parser;
+
+#[test]
+fn missing_file_uses_defaults() {
+    let config = Config::load_from(Path::new("does/not/exist.toml")).unwrap();
+    assert_eq!(config.year, 2024);
+    assert!(config.inputs_dir().ends_with("inputs"));
+    assert_eq!(config.session_file(), None);
+    assert!(!config.defaults.real);
+}
+
+#[test]
+fn reads_every_setting() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("aoc.toml");
+    fs::write(
+        &path,
+        r#"
+year = 2023
+inputs_dir = "/srv/aoc/inputs"
+session_file = "secrets/session"
+
+[defaults]
+real = true
+threshold = 10.0
+
+[requests]
+min_interval_ms = 250
+contact = "me@example.com"
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load_from(&path).unwrap();
+
+    assert_eq!(config.year, 2023);
+    assert_eq!(config.inputs_dir(), Path::new("/srv/aoc/inputs"));
+    assert!(config.session_file().unwrap().ends_with("secrets/session"));
+    assert!(config.defaults.real);
+    assert!(!config.defaults.bench);
+    assert_eq!(config.defaults.threshold, Some(10.0));
+    assert_eq!(config.min_interval(), Duration::from_millis(250));
+    assert!(config.user_agent().ends_with(" (me@example.com)"));
+}
+
+#[test]
+fn rejects_unknown_settings() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("aoc.toml");
+    fs::write(&path, "yaer = 2023\n").unwrap();
+
+    let result = Config::load_from(&path);
+
+    assert!(result.is_err());
+}
+
+/// Parses the arguments, and applies `config`'s defaults to them.
+fn opt(config: &Config, args: &[&str]) -> Opt {
+    let mut cli = Cli::try_parse_from(["aoc"].iter().chain(args)).unwrap();
+    cli.opt.apply_defaults(config);
+    cli.opt
+}
+
+#[test]
+fn defaults_count_as_passed_flags() {
+    let mut config = Config::default();
+    assert!(opt(&config, &["1", "--submit", "1"])
+        .check_requirements()
+        .is_err());
+    assert!(opt(&config, &["1", "--refresh"])
+        .check_requirements()
+        .is_err());
+    assert!(opt(&config, &["1", "--baseline", "x"])
+        .check_requirements()
+        .is_err());
+
+    config.defaults.real = true;
+    config.defaults.bench = true;
+    let submit = opt(&config, &["1", "--submit", "1", "--baseline", "x"]);
+    assert!(submit.real && submit.bench);
+    assert!(submit.check_requirements().is_ok());
+    assert!(opt(&config, &["1", "--refresh"])
+        .check_requirements()
+        .is_ok());
+
+    // fetching is about the examples, so it runs them instead of the real input
+    let fetch = opt(&config, &["1", "--fetch"]);
+    assert!(!fetch.real);
+    // and so is picking an example
+    let alt = opt(&config, &["3", "--alt", "day3_part2"]);
+    assert!(!alt.real);
+    assert!(!opt(&config, &["1", "--no-bench"]).bench);
+    assert!(Cli::try_parse_from(["aoc", "3", "--real", "--alt", "day3_part2"]).is_err());
+    assert!(Cli::try_parse_from(["aoc", "3", "--bench", "--no-bench"]).is_err());
+}
+
+#[test]
+fn bench_json_on_stdout_needs_the_text_format() {
+    let config = Config::default();
+    let check = |args: &[&str]| {
+        opt(&config, args)
+            .check_requirements()
+            .map_err(|err| err.kind())
+    };
+
+    assert!(check(&["1", "--bench", "--bench-json", "-"]).is_ok());
+    assert!(check(&[
+        "1",
+        "--bench",
+        "--bench-json",
+        "out.json",
+        "--format",
+        "json"
+    ])
+    .is_ok());
+    assert_eq!(
+        check(&["1", "--bench", "--bench-json", "-", "--format", "csv"]),
+        Err(ErrorKind::ArgumentConflict)
+    );
+}
@@ -0,0 +1,93 @@
+use std::fs;
+use std::io::{self, Write};
+
+use adventofcode_2024::{answers::Answers, config, days, runner};
+
+/// The day an example input belongs to, e.g. `day3_part2` -> 3.
+fn day_of(name: &str) -> Option<u8> {
+    let digits: String = name
+        .strip_prefix("day")?
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok()
+}
+
+/// Runs every `inputs/<year>/example/dayN*.txt` of every year through both parts and their
+/// variants, and compares against the answers stored in `inputs/<year>/example/answers.toml`.
+/// Parts without a stored answer are skipped, and inputs that only have the stub `new` adds are
+/// listed as pending.
+#[test]
+fn examples() {
+    let mut checked = 0;
+    let mut failures = vec![];
+    let mut pending = vec![];
+    for year in days::YEARS {
+        let dir = config()
+            .inputs_dir()
+            .join(year.year.to_string())
+            .join("example");
+        let answers = Answers::load(&dir.join("answers.toml")).unwrap();
+
+        for entry in fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().is_none_or(|ext| ext != "txt") {
+                continue;
+            }
+
+            let name = path.file_stem().unwrap().to_str().unwrap();
+            let Some(day) = day_of(name).and_then(|day| days::find(year.year, day)) else {
+                continue;
+            };
+
+            if answers.is_pending(name) {
+                pending.push(format!("{} {name}", year.year));
+                continue;
+            }
+
+            let input = fs::read_to_string(&path).unwrap();
+            let parsed = match runner(day.parse, input.as_str()) {
+                Ok((parsed, _)) => parsed,
+                Err(err) => {
+                    failures.push(format!("{} {name}: {err}", year.year));
+                    continue;
+                }
+            };
+            for part in [1, 2] {
+                let Some(expected) = answers.get(name, part) else {
+                    continue;
+                };
+
+                let steps = std::iter::once((format!("part {part}"), day.parts[part as usize - 1]))
+                    .chain(
+                        day.variants_of(part).map(|variant| {
+                            (format!("part {part} ({})", variant.name), variant.solve)
+                        }),
+                    );
+                for (step, solve) in steps {
+                    match runner(solve, &parsed) {
+                        Ok((answer, _)) if &answer == expected => {}
+                        Ok((answer, _)) => failures.push(format!(
+                            "{} {name} {step}: got {answer}, expected {expected}",
+                            year.year
+                        )),
+                        Err(err) => failures.push(format!("{} {name} {step}: {err}", year.year)),
+                    }
+                    checked += 1;
+                }
+            }
+        }
+    }
+
+    if !pending.is_empty() {
+        // straight to stderr, since the test harness captures `println!` and `eprintln!` and
+        // would only show them for a failing test
+        let _ = writeln!(
+            io::stderr(),
+            "pending, no example answer yet: {}",
+            pending.join(", ")
+        );
+    }
+    assert!(checked > 0, "no example answers were found");
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
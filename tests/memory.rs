@@ -0,0 +1,29 @@
+use adventofcode_2024::memory::track;
+
+#[cfg(feature = "alloc-stats")]
+#[test]
+fn counts_allocations() {
+    let (len, stats) = track(|| {
+        let kept: Vec<u64> = vec![0; 1000];
+        let mut grown = vec![];
+        for i in 0..100u64 {
+            grown.push(i);
+        }
+        kept.len() + grown.len()
+    });
+    assert_eq!(len, 1100);
+
+    // other tests run at the same time, so only lower bounds hold
+    let stats = stats.unwrap();
+    assert!(stats.allocations >= 2, "{stats}");
+    assert!(stats.bytes >= 8800, "{stats}");
+    assert!(stats.peak >= 8000, "{stats}");
+}
+
+#[cfg(not(feature = "alloc-stats"))]
+#[test]
+fn only_counts_with_the_feature() {
+    let (len, stats) = track(|| vec![0u8; 1000].len());
+    assert_eq!(len, 1000);
+    assert_eq!(stats, None);
+}
@@ -0,0 +1,89 @@
+use adventofcode_2024::{
+    client::AocClient,
+    mock::MockServer,
+    puzzle::{extract_examples, Example},
+    Answer,
+};
+
+const DAY3: &str = r#"<!DOCTYPE html>
+<html lang="en-us">
+<body>
+<main>
+<article class="day-desc"><h2>--- Day 3: Mull It Over ---</h2>
+<p>For example, consider the following section of corrupted memory:</p>
+<pre><code>x<em>mul(2,4)</em>%&amp;mul[3,7]!@^do_not_<em>mul(5,5)</em>+mul(32,64]then(<em>mul(11,8)mul(8,5)</em>)</code></pre>
+<p>Only the four highlighted sections are real <code>mul</code> instructions. Adding up the result of each instruction produces <code><em>161</em></code> (<code>2*4 + 5*5 + 11*8 + 8*5</code>).</p>
+</article>
+<p>Your puzzle answer was <code>12345</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>For example:</p>
+<pre><code>x<em>mul(2,4)</em>&amp;mul[3,7]!^<em>don't()</em>_mul(5,5)+mul(32,64](mul(11,8)un<em>do()</em>?<em>mul(8,5)</em>)</code></pre>
+<p>This time, the sum of the results is <code><em>48</em></code> (<code>2*4 + 8*5</code>).</p>
+</article>
+</main>
+</body>
+</html>
+"#;
+
+#[test]
+fn extracts_examples_and_answers() {
+    assert_eq!(
+        extract_examples(3, DAY3),
+        vec![
+            Example {
+                name: "day3".to_string(),
+                input: "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))"
+                    .to_string(),
+                answers: [Some(Answer::Int(161)), None],
+            },
+            Example {
+                name: "day3_part2".to_string(),
+                input: "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))"
+                    .to_string(),
+                answers: [None, Some(Answer::Int(48))],
+            },
+        ]
+    );
+}
+
+#[test]
+fn folds_shared_example_into_first_file() {
+    let html = r#"<article class="day-desc"><pre><code>3   4
+4   3
+</code></pre><p>The total is <code><em>11</em></code>.</p></article>
+<article class="day-desc"><p>Using the same example, the score is <code><em>31</em></code>.</p></article>"#;
+
+    let examples = extract_examples(1, html);
+    assert_eq!(examples.len(), 1);
+    assert_eq!(examples[0].name, "day1");
+    assert_eq!(examples[0].input, "3   4\n4   3\n");
+    assert_eq!(
+        examples[0].answers,
+        [Some(Answer::Int(11)), Some(Answer::Int(31))]
+    );
+}
+
+#[test]
+fn keeps_both_answers_of_a_repeated_example() {
+    let html = r#"<article class="day-desc"><pre><code>1 2
+</code></pre><p>The total is <code><em>3</em></code>.</p></article>
+<article class="day-desc"><pre><code>1 2
+</code></pre><p>Now it's <code><em>2</em></code>.</p></article>"#;
+
+    let examples = extract_examples(1, html);
+    assert_eq!(examples.len(), 1);
+    assert_eq!(
+        examples[0].answers,
+        [Some(Answer::Int(3)), Some(Answer::Int(2))]
+    );
+}
+
+#[test]
+fn downloads_puzzle_page() {
+    let mock = MockServer::start().unwrap();
+    mock.puzzle(2024, 3, DAY3);
+
+    let client = AocClient::new(&mock.url(), None);
+    let html = client.download_puzzle(2024, 3).unwrap();
+    assert_eq!(extract_examples(3, &html).len(), 2);
+}
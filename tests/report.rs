@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use adventofcode_2024::{
+    answers::Check,
+    report::{summary_table, to_csv, to_json, to_junit},
+    Answer, DayRange, DayReport, FailedDay, Selection,
+};
+
+mod common;
+use common::{day, part};
+
+#[test]
+fn parses_day_ranges() {
+    let range: DayRange = "5-9".parse().unwrap();
+    assert_eq!((range.start, range.end), (5, 9));
+    assert!(range.contains(7) && !range.contains(10));
+
+    let single: DayRange = "3".parse().unwrap();
+    assert_eq!((single.start, single.end), (3, 3));
+
+    assert!("9-5".parse::<DayRange>().is_err());
+    assert!("0".parse::<DayRange>().is_err());
+    assert!("26".parse::<DayRange>().is_err());
+    assert!("x-2".parse::<DayRange>().is_err());
+}
+
+#[test]
+fn parses_a_single_day_or_all() {
+    assert_eq!("all".parse::<Selection>().unwrap(), Selection::All);
+    assert_eq!("7".parse::<Selection>().unwrap(), Selection::Day(7));
+    assert_eq!("day25".parse::<Selection>().unwrap(), Selection::Day(25));
+
+    assert!("0".parse::<Selection>().is_err());
+    assert!("26".parse::<Selection>().is_err());
+    assert!("seven".parse::<Selection>().is_err());
+}
+
+#[test]
+fn summarises_every_part_and_the_total() {
+    let reports = [
+        day(
+            100,
+            vec![
+                part("day1", 1, Answer::Int(11), Check::Pass, 200),
+                part(
+                    "day1",
+                    2,
+                    Answer::Int(31),
+                    Check::Fail(Answer::Int(30)),
+                    300,
+                ),
+            ],
+        ),
+        day(
+            50,
+            vec![part("day3", 1, Answer::Int(161), Check::Pass, 100)],
+        ),
+        day(
+            50,
+            vec![part("day3_part2", 2, Answer::Int(48), Check::Pass, 100)],
+        ),
+        day(
+            1000,
+            vec![
+                part("day10", 1, Answer::Int(36), Check::Unknown, 2000),
+                part("day10", 2, Answer::Unimplemented, Check::Unknown, 0),
+            ],
+        ),
+    ];
+
+    assert_eq!(
+        summary_table(&reports, &[]),
+        "\
+Day | Input      | Part  | Answer          | Time     | Check
+----+------------+-------+-----------------+----------+------------------
+  1 | day1       | parse |                 | 100.00µs |
+  1 | day1       | 1     | 11              | 200.00µs | PASS
+  1 | day1       | 2     | 31              | 300.00µs | FAIL, expected 30
+  3 | day3       | parse |                 | 50.00µs  |
+  3 | day3       | 1     | 161             | 100.00µs | PASS
+  3 | day3_part2 | parse |                 | 50.00µs  |
+  3 | day3_part2 | 2     | 48              | 100.00µs | PASS
+ 10 | day10      | parse |                 | 1.00ms   |
+ 10 | day10      | 1     | 36              | 2.00ms   | UNKNOWN
+ 10 | day10      | 2     | not implemented | 0.00ns   |
+Total: 3.90ms for 3 days
+"
+    );
+}
+
+#[test]
+fn lists_failed_days_in_order() {
+    let reports = [
+        day(
+            100,
+            vec![part("day1", 1, Answer::Int(11), Check::Pass, 200)],
+        ),
+        day(
+            100,
+            vec![part("day3", 1, Answer::Int(161), Check::Pass, 200)],
+        ),
+    ];
+    let failed = [FailedDay {
+        year: 2024,
+        day: 2,
+        error: "Couldn't parse the input".to_string(),
+    }];
+
+    assert_eq!(
+        summary_table(&reports, &failed),
+        "\
+Day | Input | Part  | Answer | Time     | Check
+----+-------+-------+--------+----------+--------------------------------
+  1 | day1  | parse |        | 100.00µs |
+  1 | day1  | 1     | 11     | 200.00µs | PASS
+  2 |       |       |        |          | ERROR: Couldn't parse the input
+  3 | day3  | parse |        | 100.00µs |
+  3 | day3  | 1     | 161    | 200.00µs | PASS
+Total: 600.00µs for 2 days, 1 day failed
+"
+    );
+}
+
+fn mixed_results() -> [DayReport; 1] {
+    [day(
+        100,
+        vec![
+            part("day2", 1, Answer::Str("a,\"b\"".into()), Check::Pass, 200),
+            part("day2", 2, Answer::Int(4), Check::Fail(Answer::Int(5)), 300),
+        ],
+    )]
+}
+
+#[test]
+fn writes_a_json_object_per_part() {
+    let json: serde_json::Value =
+        serde_json::from_str(&to_json(&mixed_results(), &[]).unwrap()).unwrap();
+
+    assert_eq!(
+        json[1],
+        serde_json::json!({
+            "year": 2024,
+            "day": 2,
+            "input": "day2",
+            "part": 2,
+            "variant": null,
+            "answer": 4,
+            "status": "fail",
+            "expected": 5,
+            "error": null,
+            "time_ns": 300_000,
+        })
+    );
+    assert_eq!(json[0]["answer"], "a,\"b\"");
+}
+
+#[test]
+fn writes_a_csv_row_per_part() {
+    assert_eq!(
+        to_csv(&mixed_results(), &[]),
+        "\
+year,day,input,part,variant,answer,status,expected,error,time_ns
+2024,2,day2,1,,\"a,\"\"b\"\"\",pass,,,200000
+2024,2,day2,2,,4,fail,5,,300000
+"
+    );
+}
+
+#[test]
+fn labels_variants_with_their_part() {
+    let mut variant = part("day7", 2, Answer::Int(11387), Check::Pass, 50);
+    variant.variant = Some("recurse");
+    let reports = [day(
+        10,
+        vec![
+            part("day7", 2, Answer::Int(11387), Check::Pass, 900),
+            variant,
+        ],
+    )];
+
+    assert_eq!(reports[0].total(), Duration::from_micros(910));
+    let table = summary_table(&reports, &[]);
+    assert!(table.contains("7 | day7  | 2 (recurse) | 11387"));
+    assert!(table.contains("Total: 910.00µs for 1 day"), "{table}");
+    assert!(to_csv(&reports, &[]).ends_with("\n2024,7,day7,2,recurse,11387,pass,,,50000\n"));
+    assert!(to_junit(&reports, &[]).contains(r#"name="day7 part 2 (recurse)""#));
+}
+
+#[test]
+fn reports_wrong_answers_as_junit_failures() {
+    let mut reports = mixed_results().to_vec();
+    reports.push(day(
+        10,
+        vec![part("day5", 1, Answer::Unimplemented, Check::Unknown, 0)],
+    ));
+    let xml = to_junit(&reports, &[]);
+
+    assert!(xml.contains(r#"<testsuites name="aoc" tests="3" failures="1" skipped="1""#));
+    assert!(xml.contains(r#"<testcase classname="2024.day2" name="day2 part 1" time="0.0002"/>"#));
+    assert!(xml.contains(r#"<failure message="expected 5, got 4"/>"#));
+    assert!(xml.contains(r#"<skipped message="not implemented"/>"#));
+    assert_eq!(xml.matches("<testsuite ").count(), 2);
+}
+
+#[test]
+fn reports_failed_days_in_every_format() {
+    let reports = mixed_results();
+    let failed = [FailedDay {
+        year: 2024,
+        day: 1,
+        error: "No session token, \"log in\" first".to_string(),
+    }];
+
+    let json: serde_json::Value =
+        serde_json::from_str(&to_json(&reports, &failed).unwrap()).unwrap();
+    assert_eq!(
+        json[0],
+        serde_json::json!({
+            "year": 2024,
+            "day": 1,
+            "input": null,
+            "part": null,
+            "variant": null,
+            "answer": null,
+            "status": "error",
+            "expected": null,
+            "error": "No session token, \"log in\" first",
+            "time_ns": 0,
+        })
+    );
+    assert_eq!(json.as_array().unwrap().len(), 3);
+
+    assert!(to_csv(&reports, &failed)
+        .contains("\n2024,1,,,,,error,,\"No session token, \"\"log in\"\" first\",0\n"));
+
+    let xml = to_junit(&reports, &failed);
+    assert!(xml.contains(r#"<testsuites name="aoc" tests="3" failures="1" skipped="0" errors="1""#));
+    assert!(xml.contains(r#"<error message="No session token, &quot;log in&quot; first"/>"#));
+    assert!(xml.find("2024 day 1").unwrap() < xml.find("2024 day 2").unwrap());
+}
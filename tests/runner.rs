@@ -0,0 +1,291 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use adventofcode_2024::{
+    answers::Check,
+    config, days,
+    error::{catch_panic, Error},
+    example_files, run, runner, Answer, Cli, Day, Solution,
+};
+use anyhow::{anyhow, Result};
+use clap::Parser;
+use rayon::prelude::*;
+
+struct Fallible;
+
+impl Solution for Fallible {
+    const DAY: u8 = 1;
+    type Input = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input.lines().map(|line| Ok(line.parse()?)).collect()
+    }
+
+    fn part1(input: &Self::Input) -> Result<Answer> {
+        input
+            .iter()
+            .max()
+            .map(|&n| n.into())
+            .ok_or_else(|| anyhow!("no numbers to pick from"))
+    }
+
+    fn part2(input: &Self::Input) -> Result<Answer> {
+        Ok((input[0] / input[1]).into())
+    }
+}
+
+/// Parses the input, and runs one part against it.
+fn solve(day: &Day, part: usize, input: &str) -> Result<Answer, Error> {
+    let (parsed, _) = runner(day.parse, input)?;
+    let (answer, _) = runner(day.parts[part - 1], &parsed)?;
+    Ok(answer)
+}
+
+#[test]
+fn reports_bad_input_without_panicking() {
+    let day = days::find(2024, 2).unwrap();
+
+    let err = solve(day, 1, "7 6 4\n1 2 x\n").unwrap_err();
+    assert!(matches!(err, Error::Parse(_)), "{err}");
+    assert!(
+        err.to_string()
+            .ends_with("Report 2 has a bad level \"x\": invalid digit found in string"),
+        "{err}"
+    );
+}
+
+#[test]
+fn runs_fallible_parts() {
+    let day = Day::new::<Fallible>();
+
+    assert_eq!(solve(&day, 1, "3\n7\n").unwrap(), Answer::Int(7));
+}
+
+#[test]
+fn reports_parse_and_solution_errors() {
+    let day = Day::new::<Fallible>();
+
+    let err = solve(&day, 1, "3\nseven\n").unwrap_err();
+    assert!(matches!(err, Error::Parse(_)), "{err}");
+    assert_eq!(err.exit_code(), 6);
+
+    let err = solve(&day, 1, "").unwrap_err();
+    assert!(matches!(err, Error::Solution(_)), "{err}");
+    assert_eq!(err.exit_code(), 7);
+}
+
+#[test]
+fn catches_panicking_parts() {
+    let day = Day::new::<Fallible>();
+
+    // dividing by zero
+    let err = solve(&day, 2, "3\n0\n").unwrap_err();
+    assert!(matches!(err, Error::Solution(_)), "{err}");
+    assert!(
+        err.to_string().contains("panicked at tests/runner.rs"),
+        "{err}"
+    );
+}
+
+#[test]
+fn catches_panics_on_rayon_threads() {
+    let err = catch_panic(|| {
+        (0..64).into_par_iter().for_each(|n| {
+            if n == 42 {
+                panic!("no {n}s allowed");
+            }
+        })
+    })
+    .unwrap_err();
+    assert!(
+        err.to_string().starts_with("panicked at tests/runner.rs"),
+        "{err}"
+    );
+    assert!(err.to_string().ends_with("no 42s allowed"), "{err}");
+}
+
+#[test]
+fn leaves_panics_on_other_threads_alone() {
+    let err = catch_panic(|| {
+        // not a rayon worker, so this panic is printed and isn't this call's
+        assert!(std::thread::spawn(|| panic!("elsewhere")).join().is_err());
+        // resuming doesn't run the panic hook, so there's no location of its own
+        std::panic::resume_unwind(Box::new("resumed"))
+    })
+    .unwrap_err();
+    assert_eq!(err.to_string(), "panicked: resumed");
+}
+
+#[test]
+fn reports_a_panic_only_as_the_error() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_adventofcode-2024"))
+        .args(["1", "--input", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"a b\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(6));
+    assert!(
+        stderr.starts_with("Error: Parsing the input failed: panicked at "),
+        "{stderr}"
+    );
+    assert_eq!(stderr.lines().count(), 1, "{stderr}");
+}
+
+#[test]
+fn reports_a_failing_day_in_the_format() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_adventofcode-2024"))
+        .args(["1", "--input", "-", "--format", "json"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"a b\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert_eq!(output.status.code(), Some(6));
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let [record] = &json.as_array().unwrap()[..] else {
+        panic!("expected a single record: {json}");
+    };
+    assert_eq!(record["day"], 1);
+    assert_eq!(record["status"], "error");
+    assert!(record["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Parsing the input failed"));
+}
+
+#[test]
+fn keeps_stdout_to_the_bench_json() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_adventofcode-2024"))
+        .args(["1", "--input", "-", "--bench", "--bench-json", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"3   4\n4   3\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    // parsing, then both parts
+    assert_eq!(json.as_array().unwrap().len(), 3);
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Day 1 Part 1: 0"));
+}
+
+#[test]
+fn exits_with_8_on_a_regression() {
+    // a baseline where part 1 took a nanosecond, which no run can keep up with
+    let name = "runner-regression";
+    let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join(".aoc/baselines")
+        .join(name)
+        .with_extension("json");
+    let record = serde_json::json!({
+        "year": 2024, "day": 1, "input": "stdin", "part": 1, "iterations": 10,
+        "min_ns": 1, "median_ns": 1, "mean_ns": 1, "stddev_ns": 0, "p95_ns": 1,
+    });
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(
+        &path,
+        serde_json::json!({ "2024/input/stdin/part1": record }).to_string(),
+    )
+    .unwrap();
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_adventofcode-2024"))
+        .args(["1", "--input", "-", "--bench", "--baseline", name])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"3   4\n4   3\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let stderr = String::from_utf8_lossy(&output.stderr);
+
+    assert_eq!(output.status.code(), Some(8), "{stderr}");
+    assert!(stderr.contains("1 part(s) regressed"), "{stderr}");
+}
+
+#[test]
+fn shares_one_parse_between_parts() {
+    let day = Day::new::<Fallible>();
+
+    let (parsed, _) = runner(day.parse, "8\n2\n").unwrap();
+    let (part1, _) = runner(day.parts[0], &parsed).unwrap();
+    let (part2, _) = runner(day.parts[1], &parsed).unwrap();
+    assert_eq!((part1, part2), (Answer::Int(8), Answer::Int(4)));
+}
+
+#[test]
+fn runs_a_custom_input_without_checking_it() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("input.txt");
+    std::fs::write(&path, "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+
+    let mut cli = Cli::try_parse_from(["aoc", "1", "--input", path.to_str().unwrap()]).unwrap();
+    cli.opt.apply_defaults(config());
+    let report = run(days::find(2024, 1).unwrap(), &cli.opt);
+
+    let [report] = &report.unwrap()[..] else {
+        panic!("a custom input should run once");
+    };
+    assert_eq!(report.input, path.file_stem().unwrap().to_str().unwrap());
+    assert_eq!(report.parts[0].answer, 11i64.into());
+    assert_eq!(report.parts[1].answer, 31i64.into());
+    assert!(report.parts.iter().all(|part| part.check == Check::Unknown));
+}
+
+#[test]
+fn matches_examples_to_their_parts() {
+    let names = [
+        "day3_part2",
+        "day3",
+        "day30",
+        "day3_small",
+        "day4",
+        "day3_part2_b",
+        "day3_parts",
+    ];
+    let files = example_files(3, &names);
+
+    let parts: Vec<_> = files
+        .iter()
+        .map(|file| (file.name.as_str(), file.parts.as_slice()))
+        .collect();
+    assert_eq!(
+        parts,
+        [
+            ("day3", &[1][..]),
+            ("day3_part2", &[2]),
+            ("day3_part2_b", &[2]),
+            ("day3_parts", &[1]),
+            ("day3_small", &[1]),
+        ]
+    );
+
+    // without part-specific files, every example runs both parts
+    assert!(example_files(4, &names)
+        .iter()
+        .all(|file| file.parts == [1, 2]));
+    assert!(example_files(5, &names).is_empty());
+}
@@ -0,0 +1,119 @@
+use adventofcode_2024::answers::Answers;
+use adventofcode_2024::scaffold::{register_day, register_year, render_template, render_year};
+use adventofcode_2024::Answer;
+
+const MOD_RS: &str = "use crate::Day;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day9;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day9::Day9>(),
+    Day::new::<day10::Day10>(),
+];
+";
+
+#[test]
+fn registers_day_in_order() {
+    let registered = register_day(MOD_RS, 3).unwrap();
+    assert_eq!(
+        registered,
+        "use crate::Day;
+
+pub mod day1;
+pub mod day10;
+pub mod day2;
+pub mod day3;
+pub mod day9;
+
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(),
+    Day::new::<day2::Day2>(),
+    Day::new::<day3::Day3>(),
+    Day::new::<day9::Day9>(),
+    Day::new::<day10::Day10>(),
+];
+"
+    );
+
+    let registered = register_day(MOD_RS, 12).unwrap();
+    assert!(registered.contains("pub mod day10;\npub mod day12;\npub mod day2;"));
+    assert!(registered.contains("Day::new::<day10::Day10>(),\n    Day::new::<day12::Day12>(),\n];"));
+}
+
+#[test]
+fn refuses_to_register_twice() {
+    assert!(register_day(MOD_RS, 9).is_err());
+}
+
+#[test]
+fn renders_template_for_day() {
+    let source = render_template(12).unwrap();
+    assert!(source.contains("pub struct Day12;"));
+    assert!(source.contains("impl Solution for Day12 {"));
+    assert!(source.contains("const DAY: u8 = 12;"));
+    assert!(!source.contains("DayX"));
+}
+
+#[test]
+fn registers_first_day_of_a_year() {
+    let registered = register_day(&render_year(2023), 1).unwrap();
+    assert_eq!(
+        registered,
+        "use crate::Day;
+
+pub mod day1;
+
+/// Every solved day of 2023, in the order that `all` runs them.
+pub const DAYS: &[Day] = &[
+    Day::new::<day1::Day1>(),
+];
+"
+    );
+}
+
+#[test]
+fn registers_year_in_a_single_line_list() {
+    let mod_rs = "use crate::Day;
+
+pub mod y2024;
+
+pub const YEARS: &[Year] = &[Year::new(2024, y2024::DAYS)];
+";
+    let registered = register_year(mod_rs, 2023).unwrap();
+    assert_eq!(
+        registered,
+        "use crate::Day;
+
+pub mod y2023;
+pub mod y2024;
+
+pub const YEARS: &[Year] = &[
+    Year::new(2023, y2023::DAYS),
+    Year::new(2024, y2024::DAYS),
+];
+"
+    );
+    assert!(register_year(mod_rs, 2024).is_err());
+}
+
+#[test]
+fn stubs_stay_pending_until_answered() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("stub.toml");
+    let mut answers = Answers::default();
+    answers.stub("day12");
+    answers.save(&path).unwrap();
+
+    let mut answers = Answers::load(&path).unwrap();
+    assert!(answers.contains("day12") && answers.is_pending("day12"));
+
+    answers.set("day12", 1, Answer::Int(7));
+    answers.stub("day12");
+    assert!(!answers.is_pending("day12"));
+    assert_eq!(answers.get("day12", 1), Some(&Answer::Int(7)));
+}
@@ -0,0 +1,30 @@
+use std::fs;
+
+use adventofcode_2024::session::{clean_token, read_token, save_token};
+
+#[test]
+fn cleans_pasted_tokens() {
+    assert_eq!(clean_token("  53616c74\n").unwrap(), "53616c74");
+    assert_eq!(clean_token("session=53616c74").unwrap(), "53616c74");
+    assert!(clean_token(" \n").is_err());
+    assert!(clean_token("5361 6c74").is_err());
+}
+
+#[test]
+fn saves_the_token_for_the_user_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("aoc").join("session");
+    save_token(&path, "old").unwrap();
+    save_token(&path, "53616c74").unwrap();
+
+    let token = read_token(&path);
+    #[cfg(unix)]
+    let mode = {
+        use std::os::unix::fs::PermissionsExt;
+        fs::metadata(&path).unwrap().permissions().mode() & 0o777
+    };
+
+    assert_eq!(token.as_deref(), Some("53616c74"));
+    #[cfg(unix)]
+    assert_eq!(mode, 0o600);
+}
@@ -0,0 +1,50 @@
+use std::time::Duration;
+
+use adventofcode_2024::{
+    submit::{Guard, Outcome},
+    Answer,
+};
+
+fn page(text: &str) -> String {
+    format!("<!DOCTYPE html>\n<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>")
+}
+
+#[test]
+fn parses_submission_outcomes() {
+    let cases = [
+        ("That's the right answer!  You are one gold star closer to finding the Chief Historian.", Outcome::Correct),
+        ("That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data.", Outcome::TooHigh),
+        ("That's not the right answer; your answer is too low.  Please wait one minute before trying again.", Outcome::TooLow),
+        ("That's not the right answer.  If you're stuck, make sure you're using the full input data.", Outcome::Wrong),
+        ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.", Outcome::RateLimited(Duration::from_secs(34))),
+        ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 2s left to wait.", Outcome::RateLimited(Duration::from_secs(242))),
+        ("You don't seem to be solving the right level.  Did you already complete it?", Outcome::AlreadySolved),
+    ];
+
+    for (text, outcome) in cases {
+        assert_eq!(Outcome::parse(&page(text)).unwrap(), outcome, "{text}");
+    }
+}
+
+#[test]
+fn rejects_unknown_pages() {
+    assert!(Outcome::parse(&page("Something else entirely")).is_err());
+}
+
+#[test]
+fn guard_refuses_known_wrong_and_out_of_bounds_answers() {
+    let mut guard = Guard::default();
+    let key = "2024/day1/part1";
+
+    guard.record(key, &Answer::Int(100), Outcome::TooHigh);
+    guard.record(key, &Answer::Int(10), Outcome::TooLow);
+    guard.record(key, &Answer::Int(50), Outcome::Wrong);
+
+    assert!(guard.check(key, &Answer::Int(100)).is_err());
+    assert!(guard.check(key, &Answer::Int(150)).is_err());
+    assert!(guard.check(key, &Answer::Int(10)).is_err());
+    assert!(guard.check(key, &Answer::Int(5)).is_err());
+    assert!(guard.check(key, &Answer::Int(50)).is_err());
+    assert!(guard.check(key, &Answer::Int(42)).is_ok());
+    assert!(guard.check("2024/day1/part2", &Answer::Int(100)).is_ok());
+}
@@ -0,0 +1,35 @@
+use std::time::{Duration, UNIX_EPOCH};
+
+use adventofcode_2024::unlock::{format_countdown, time_until_unlock, unlock_time};
+
+#[test]
+fn unlocks_at_midnight_eastern() {
+    // 2024-12-01T05:00:00Z
+    assert_eq!(
+        unlock_time(2024, 1),
+        UNIX_EPOCH + Duration::from_secs(1_733_029_200)
+    );
+    // 2015-12-25T05:00:00Z
+    assert_eq!(
+        unlock_time(2015, 25),
+        UNIX_EPOCH + Duration::from_secs(1_451_019_600)
+    );
+}
+
+#[test]
+fn counts_down_to_the_unlock() {
+    let unlock = unlock_time(2024, 5);
+    let early = unlock - Duration::from_secs(26 * 60 * 60 + 61);
+
+    let remaining = time_until_unlock(2024, 5, early).unwrap();
+    assert_eq!(format_countdown(remaining), "1d 02h 01m 01s");
+    assert_eq!(
+        format_countdown(Duration::from_millis(59_500)),
+        "00h 01m 00s"
+    );
+    assert_eq!(time_until_unlock(2024, 5, unlock), None);
+    assert_eq!(
+        time_until_unlock(2024, 5, unlock + Duration::from_secs(1)),
+        None
+    );
+}
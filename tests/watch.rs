@@ -0,0 +1,90 @@
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use adventofcode_2024::watch::{diff_answers, parse_answers, CHILD_ENV};
+
+const RUN: &str = "\
+---
+Day 3 Input: day3
+Day 3 Parse: 36.76µs
+---
+Day 3 Part 1: 161 [PASS]
+--- 15.78µs
+---
+Day 3 Total: 52.54µs (parse 70.0% | part 1 30.0%)
+---
+Day 3 Input: day3_part2
+Day 3 Parse: 30.12µs
+---
+Day 3 Part 2: not implemented
+--- 120ns
+---
+Day 3 Total: 30.24µs (parse 99.6% | part 2 0.4%)
+";
+
+fn key(input: &str, part: u8) -> (u8, String, u8) {
+    (3, input.to_string(), part)
+}
+
+#[test]
+fn parses_answers_from_a_run() {
+    let answers = parse_answers(RUN);
+
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers[&key("day3", 1)], "161");
+    assert_eq!(answers[&key("day3_part2", 2)], "not implemented");
+}
+
+#[test]
+fn diffs_changed_and_new_answers() {
+    let previous = parse_answers(RUN);
+    let current = parse_answers(
+        "\
+Day 3 Input: day3
+Day 3 Part 1: 161 [PASS]
+Day 3 Part 2: 12 [UNKNOWN]
+Day 3 Input: day3_part2
+Day 3 Part 2: 47 [FAIL, expected 48]
+",
+    );
+
+    assert_eq!(
+        diff_answers(&previous, &current),
+        [
+            "Day 3 Part 2 (day3): new answer 12",
+            "Day 3 Part 2 (day3_part2): not implemented -> 47",
+        ]
+    );
+    assert!(diff_answers(&current, &current).is_empty());
+}
+
+/// The runs that `--watch` starts get the same arguments, however the flag was spelled, so they
+/// have to run the day once rather than start watching themselves.
+#[test]
+fn watched_runs_run_once() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_adventofcode-2024"))
+        .args(["1", "-w"])
+        .env(CHILD_ENV, "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait().unwrap() {
+            break status;
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            child.kill().unwrap();
+            panic!("the run started watching instead of running once");
+        }
+        thread::sleep(Duration::from_millis(50));
+    };
+
+    let output = child.wait_with_output().unwrap();
+    assert!(status.success());
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+    assert_eq!(answers[&(1, "day1".to_string(), 1)], "11");
+}